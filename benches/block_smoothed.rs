@@ -0,0 +1,69 @@
+//! Cost of smoothing an SVF's coefficients over fixed-length blocks with [`BlockSmoothed`],
+//! versus ramping them with [`Ramp`]s, whose length is only known at runtime.
+//!
+//! Run with `cargo bench --bench block_smoothed`.
+
+#![feature(portable_simd, test)]
+
+extern crate test;
+
+#[path = "common/square.rs"]
+mod square;
+
+use filte::{
+    smooth::Ramp,
+    svf::{FilterShape, SvfCoeffs, SVF},
+    wrappers::BlockSmoothed,
+};
+use square::square;
+use std::simd::Simd;
+use test::{black_box, Bencher};
+
+const BLOCK_SIZE: usize = 64;
+const BLOCKS: usize = 8;
+
+/// Coefficients alternating between two settings, from one block to the next
+fn target(block: usize) -> SvfCoeffs<8> {
+    let g = if block.is_multiple_of(2) { 0.05 } else { 0.2 };
+    SvfCoeffs::new(Simd::splat(g), Simd::splat(0.5))
+}
+
+#[bench]
+fn block_smoothed(b: &mut Bencher) {
+    let mut filter = BlockSmoothed::<_, BLOCK_SIZE, 8>::new(SVF::default(), target(1));
+    let input: [_; BLOCK_SIZE] = core::array::from_fn(square);
+
+    b.iter(|| {
+        for block in 0..BLOCKS {
+            let output = filter.process_block(
+                black_box(&input),
+                black_box(target(block)),
+                FilterShape::Lowpass,
+            );
+            black_box(output);
+        }
+    });
+}
+
+#[bench]
+fn ramped(b: &mut Bencher) {
+    let mut svf = SVF::<8>::default();
+    let start = target(1);
+    let (mut g, mut res) = (Ramp::new(start.g), Ramp::new(start.res));
+    let input: [_; BLOCK_SIZE] = core::array::from_fn(square);
+
+    b.iter(|| {
+        for block in 0..BLOCKS {
+            let target = black_box(target(block));
+            let len = black_box(BLOCK_SIZE);
+            g.set_target(target.g, len);
+            res.set_target(target.res, len);
+
+            let output: [_; BLOCK_SIZE] = core::array::from_fn(|i| {
+                svf.process(black_box(input[i]), g.tick(), res.tick());
+                *svf.get_lowpass()
+            });
+            black_box(output);
+        }
+    });
+}
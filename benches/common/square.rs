@@ -0,0 +1,8 @@
+//! Input signal shared by the benches.
+
+use std::simd::Simd;
+
+/// Square wave input, a constant one would let the states decay to subnormals
+pub fn square(i: usize) -> Simd<f32, 8> {
+    Simd::splat(if i % 64 < 32 { 1. } else { -1. })
+}
@@ -0,0 +1,44 @@
+//! Cost of [`SVF::process_clamped`], with coefficients in range, over [`SVF::process`]:
+//! only the range check is inlined, so both should run at (nearly) the same speed.
+//!
+//! Run with `cargo bench --bench process_clamped`.
+
+#![feature(portable_simd, test)]
+
+extern crate test;
+
+#[path = "common/square.rs"]
+mod square;
+
+use filte::svf::SVF;
+use square::square;
+use std::simd::Simd;
+use test::{black_box, Bencher};
+
+const BLOCK_SIZE: usize = 512;
+
+#[bench]
+fn process(b: &mut Bencher) {
+    let mut svf = SVF::<8>::default();
+    let (g, res) = (Simd::splat(0.1), Simd::splat(0.5));
+
+    b.iter(|| {
+        for i in 0..BLOCK_SIZE {
+            svf.process(black_box(square(i)), black_box(g), black_box(res));
+        }
+        black_box(svf.get_lowpass());
+    });
+}
+
+#[bench]
+fn process_clamped(b: &mut Bencher) {
+    let mut svf = SVF::<8>::default();
+    let (g, res) = (Simd::splat(0.1), Simd::splat(0.5));
+
+    b.iter(|| {
+        for i in 0..BLOCK_SIZE {
+            svf.process_clamped(black_box(square(i)), black_box(g), black_box(res));
+        }
+        black_box(svf.get_lowpass());
+    });
+}
@@ -0,0 +1,59 @@
+//! Cost of prewarping the cutoff of a sweeping SVF every sample, versus ramping `g`
+//! linearly between prewarped block endpoints with [`Ramp`].
+//!
+//! Run with `cargo bench --bench ramp`.
+
+#![feature(portable_simd, test)]
+
+extern crate test;
+
+#[path = "common/square.rs"]
+mod square;
+
+use filte::{
+    smooth::Ramp,
+    svf::{g_from_hz, SVF},
+};
+use square::square;
+use std::simd::Simd;
+use test::{black_box, Bencher};
+
+const SAMPLE_RATE: f32 = 48000.;
+const BLOCK_SIZE: usize = 512;
+const FREQ_START: f32 = 200.;
+const FREQ_END: f32 = 8000.;
+
+#[bench]
+fn per_sample_g_from_hz(b: &mut Bencher) {
+    let mut svf = SVF::<8>::default();
+    let res = Simd::splat(0.5);
+    let step = (FREQ_END - FREQ_START) / BLOCK_SIZE as f32;
+
+    b.iter(|| {
+        for i in 0..BLOCK_SIZE {
+            let freq = Simd::splat((i as f32).mul_add(step, FREQ_START));
+            let g = g_from_hz(black_box(freq), SAMPLE_RATE);
+            svf.process(black_box(square(i)), g, res);
+        }
+        black_box(svf.get_lowpass());
+    });
+}
+
+#[bench]
+fn ramped_g(b: &mut Bencher) {
+    let mut svf = SVF::<8>::default();
+    let res = Simd::splat(0.5);
+
+    b.iter(|| {
+        let start = g_from_hz(black_box(Simd::splat(FREQ_START)), SAMPLE_RATE);
+        let end = g_from_hz(black_box(Simd::splat(FREQ_END)), SAMPLE_RATE);
+        let mut ramp = Ramp::new(start);
+        ramp.set_target(end, BLOCK_SIZE);
+
+        for i in 0..BLOCK_SIZE {
+            let g = ramp.tick();
+            svf.process(black_box(square(i)), g, res);
+        }
+        black_box(svf.get_lowpass());
+    });
+}
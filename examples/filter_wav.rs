@@ -0,0 +1,107 @@
+//! Filter a mono or stereo WAV file with an SVF.
+//!
+//! Usage: `cargo run --example filter_wav -- <input.wav> <output.wav> <shape> <cutoff_hz> <q>`
+//!
+//! `shape` is one of `lowpass`, `bandpass`, `highpass`, `allpass` or `notch`.
+//! The output is written as 32-bit float.
+
+#![feature(portable_simd)]
+
+use std::{env, error::Error, process};
+
+use filte::{
+    svf::{g_from_hz, res_from_q, FilterShape, SvfCoeffs, SVF},
+    Filter,
+};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::simd::Simd;
+
+/// Number of frames processed between coefficient updates
+const BLOCK_SIZE: usize = 512;
+
+fn parse_shape(name: &str) -> Option<FilterShape<()>> {
+    Some(match name {
+        "lowpass" => FilterShape::Lowpass,
+        "bandpass" => FilterShape::UnitBandpass,
+        "highpass" => FilterShape::Highpass,
+        "allpass" => FilterShape::Allpass,
+        "notch" => FilterShape::Notch,
+        _ => return None,
+    })
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [input, output, shape, cutoff, q] = args else {
+        return Err("usage: filter_wav <input.wav> <output.wav> <shape> <cutoff_hz> <q>".into());
+    };
+
+    let shape = parse_shape(shape).ok_or("unknown shape")?;
+    let cutoff: f32 = cutoff.parse()?;
+    let q: f32 = q.parse()?;
+
+    let mut reader = WavReader::open(input)?;
+    let spec = reader.spec();
+    let channels = usize::from(spec.channels);
+
+    if !(1..=2).contains(&channels) {
+        return Err("only mono and stereo files are supported".into());
+    }
+
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let mut writer = WavWriter::create(
+        output,
+        WavSpec {
+            sample_format: SampleFormat::Float,
+            bits_per_sample: 32,
+            ..spec
+        },
+    )?;
+
+    // one lane per channel, the second one is unused for mono files
+    let mut svf = SVF::<2>::default();
+    let shape = shape.map(|()| Simd::splat(1.));
+    let sample_rate = spec.sample_rate as f32;
+
+    for block in samples.chunks(BLOCK_SIZE * channels) {
+        // the coefficients are constant here, but this is where
+        // they would be updated for automation or modulation
+        let coeffs = SvfCoeffs::new(
+            g_from_hz(Simd::splat(cutoff), sample_rate),
+            res_from_q(Simd::splat(q)),
+        );
+
+        for frame in block.chunks(channels) {
+            let mut x = [0.; 2];
+            x[..channels].copy_from_slice(frame);
+
+            Filter::process(&mut svf, Simd::from_array(x), &coeffs);
+            let y = svf.get(shape).to_array();
+
+            for &sample in &y[..channels] {
+                writer.write_sample(sample)?;
+            }
+        }
+    }
+
+    writer.finalize()?;
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(e) = run(&args) {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+use svf::FilterShape;
+
+/// Coefficients of a biquad filter, normalized such that `a0 = 1`:
+///
+/// `H(z) = (b0 + b1 z^-1 + b2 z^-2) / (1 + a1 z^-1 + a2 z^-2)`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Biquad<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    pub b0: VFloat<N>,
+    pub b1: VFloat<N>,
+    pub b2: VFloat<N>,
+    pub a1: VFloat<N>,
+    pub a2: VFloat<N>,
+}
+
+/// Coefficients of the polynomial `c0 + c1 z^-1 + c2 z^-2` obtained by applying the bilinear
+/// transform `s = (1 - z^-1) / (g * (1 + z^-1))` to `n2 s^2 + n1 s + n0`, and multiplying by
+/// `g^2 (1 + z^-1)^2`
+#[inline]
+fn bilinear<const N: usize>([n2, n1, n0]: [VFloat<N>; 3], g: VFloat<N>) -> [VFloat<N>; 3]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let g2 = g * g;
+    let (even, odd) = (n0.mul_add(g2, n2), n1 * g);
+    let two = Simd::splat(2.);
+    [even + odd, two * n0.mul_add(g2, -n2), even - odd]
+}
+
+/// Coefficients `[n2, n1, n0]` of the numerator of the prototype transfer function of the
+/// output of an SVF selected by `shape`, over the denominator `s^2 + res * s + 1`
+#[inline]
+fn svf_numerator<const N: usize>(shape: FilterShape<VFloat<N>>, res: VFloat<N>) -> [VFloat<N>; 3]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (zero, one) = (Simd::splat(0.), Simd::splat(1.));
+    match shape {
+        FilterShape::Passthrough => [one, res, one],
+        FilterShape::Lowpass => [zero, zero, one],
+        FilterShape::Bandpass => [zero, one, zero],
+        FilterShape::UnitBandpass => [zero, res, zero],
+        FilterShape::Highpass => [one, zero, zero],
+        FilterShape::Allpass => [-one, res, -one],
+        FilterShape::Notch => [one, zero, one],
+        FilterShape::LowShelf(m) => [one, m * res, m * m],
+        FilterShape::BandShelf(m) => [one, m * res, one],
+        FilterShape::HighShelf(m) => [m * m, m * res, one],
+    }
+}
+
+impl<const N: usize> Biquad<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Normalize numerator and denominator coefficients such that `a0 = 1`
+    #[inline]
+    fn from_polynomials([b0, b1, b2]: [VFloat<N>; 3], [a0, a1, a2]: [VFloat<N>; 3]) -> Self {
+        let inv_a0 = a0.recip();
+        Self {
+            b0: b0 * inv_a0,
+            b1: b1 * inv_a0,
+            b2: b2 * inv_a0,
+            a1: a1 * inv_a0,
+            a2: a2 * inv_a0,
+        }
+    }
+
+    /// The biquad with the same transfer function as the output of an
+    /// [`SVF`](svf::SVF) selected by `shape`, with the coefficients `g` and `res`.
+    #[inline]
+    pub fn from_svf(shape: FilterShape<VFloat<N>>, g: VFloat<N>, res: VFloat<N>) -> Self {
+        let one = Simd::splat(1.);
+        Self::from_polynomials(
+            bilinear(svf_numerator(shape, res), g),
+            bilinear([one, res, one], g),
+        )
+    }
+}
+
+/// The biquad undoing the output of an [`SVF`](svf::SVF) selected by `shape`, with the
+/// coefficients `g` and `res`, i.e. whose transfer function is its inverse, swapping its
+/// poles and zeros.
+///
+/// The inverse is only stable if the original filter's zeros lie strictly inside the unit
+/// circle, i.e. if it is minimum phase. This is the case for the shelving shapes (with a
+/// positive gain), whose inverses are the same shelves with the inverse gain. It isn't the
+/// case for the others: the lowpass has a double zero at nyquist, the highpass at DC, the
+/// bandpasses one at each, and the notch a pair on the unit circle, all of which, infinitely
+/// attenuated by the original filter, would be infinitely boosted by the inverse. The
+/// allpass' zeros lie outside of the unit circle, making its inverse unstable.
+#[cfg(feature = "num")]
+#[inline]
+pub fn inverse_coeffs<const N: usize>(
+    shape: FilterShape<VFloat<N>>,
+    g: VFloat<N>,
+    res: VFloat<N>,
+) -> Biquad<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let one = Simd::splat(1.);
+    Biquad::from_polynomials(
+        bilinear([one, res, one], g),
+        bilinear(svf_numerator(shape, res), g),
+    )
+}
+
+/// State of a biquad filter, in transposed direct form II
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BiquadFilter<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    s1: VFloat<N>,
+    s2: VFloat<N>,
+}
+
+impl<const N: usize> BiquadFilter<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s1 = Simd::splat(0.);
+        self.s2 = Simd::splat(0.);
+    }
+
+    /// Feed the input sample `x`, and return the filter's output
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, coeffs: &Biquad<N>) -> VFloat<N> {
+        let y = coeffs.b0.mul_add(x, self.s1);
+        self.s1 = coeffs.b1.mul_add(x, self.s2) - coeffs.a1 * y;
+        self.s2 = coeffs.b2 * x - coeffs.a2 * y;
+        y
+    }
+}
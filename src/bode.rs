@@ -0,0 +1,73 @@
+use super::*;
+
+/// Lowest magnitude, in dB, returned by [`magnitude_db`], i.e. a linear magnitude of
+/// `1e-15`, way below audibility, and around the rounding error of `f64` responses.
+pub const MAGNITUDE_DB_FLOOR: f64 = -300.;
+
+/// Magnitude of `c`, in dB, floored to [`MAGNITUDE_DB_FLOOR`], so that zeros of a response
+/// (e.g. a notch filter at its center frequency) give finite values, instead of `-inf`.
+#[inline]
+pub fn magnitude_db<T: Float>(c: Complex<T>) -> T {
+    let floor = T::from(MAGNITUDE_DB_FLOOR).unwrap();
+    let db = T::from(20.).unwrap() * c.norm().log10();
+    // NaNs fail the comparison and are propagated
+    if db < floor {
+        floor
+    } else {
+        db
+    }
+}
+
+/// Phase of `c`, in degrees, in `[-180 ; 180]`
+#[inline]
+pub fn phase_degrees<T: Float>(c: Complex<T>) -> T {
+    c.arg().to_degrees()
+}
+
+/// Magnitude (in dB, as in [`magnitude_db`]) and phase (in degrees) of `c`
+#[inline]
+pub fn bode<T: Float>(c: Complex<T>) -> (T, T) {
+    (magnitude_db(c), phase_degrees(c))
+}
+
+/// Group delay, in samples, of a digital filter with the frequency `response`, at the
+/// normalized frequency `omega`, i.e. the negated derivative of the phase of `response`
+/// with respect to `omega`.
+///
+/// The derivative is approximated with a central difference, taken as the phase of the
+/// ratio of the two responses, rather than the difference of their phases, which is
+/// immune to phase wrapping. It is undefined at the zeros of the response (e.g. the
+/// center of a notch), where the phase jumps.
+#[inline]
+pub fn group_delay(response: impl Fn(f64) -> Complex<f64>, omega: f64) -> f64 {
+    const H: f64 = 1e-5;
+    let hi = response(omega + H);
+    let lo = response(omega - H);
+    -(hi * lo.conj()).arg() / (2. * H)
+}
+
+/// Sample the frequency `response` of a digital filter (a function of the normalized
+/// frequency) at `points` frequencies, log-spaced from `f_lo` to `f_hi` (both included,
+/// in Hz, with `0 < f_lo`, and `f_hi < sample_rate / 2`), returning, for each,
+/// `(freq_hz, magnitude_db, phase_degrees)`, as given by [`bode()`], e.g. for plotting.
+#[cfg(feature = "std")]
+#[inline]
+pub fn sweep(
+    response: impl Fn(f64) -> Complex<f64>,
+    sample_rate: f64,
+    f_lo: f64,
+    f_hi: f64,
+    points: usize,
+) -> Vec<(f64, f64, f64)> {
+    let ratio = f_hi / f_lo;
+    let step = if points > 1 { (points - 1) as f64 } else { 1. };
+
+    (0..points)
+        .map(|i| {
+            let freq = f_lo * ratio.powf(i as f64 / step);
+            let omega = core::f64::consts::TAU * freq / sample_rate;
+            let (mag, phase) = bode(response(omega));
+            (freq, mag, phase)
+        })
+        .collect()
+}
@@ -0,0 +1,225 @@
+use super::*;
+
+use core::f32::consts::SQRT_2;
+use svf::{g_from_hz, SVF};
+
+/// 4th order Linkwitz-Riley crossover, splitting a signal into a low and a high band, which
+/// sum back to the input passed through a 2nd order allpass (i.e. a flat magnitude response):
+/// that of an SVF with the same `g` and `res = sqrt(2)`, with the opposite sign.
+///
+/// Each band is the square of the corresponding output of a Butterworth SVF, a first SVF
+/// producing both the lowpass and the highpass, each going through its own second SVF.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Crossover<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    split: SVF<N>,
+    lp: SVF<N>,
+    hp: SVF<N>,
+}
+
+impl<const N: usize> Crossover<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        for svf in [&mut self.split, &mut self.lp, &mut self.hp] {
+            svf.reset();
+        }
+    }
+
+    /// Process the input sample `x`, and return the low and high bands, in that order.
+    ///
+    /// `g` sets the crossover frequency, see [`SVF::process`]
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, g: VFloat<N>) -> (VFloat<N>, VFloat<N>) {
+        let res = Simd::splat(SQRT_2);
+
+        self.split.process(x, g, res);
+        self.lp.process(*self.split.get_lowpass(), g, res);
+        self.hp.process(*self.split.get_highpass(), g, res);
+
+        (*self.lp.get_lowpass(), *self.hp.get_highpass())
+    }
+}
+
+/// Splits a signal into `BANDS` contiguous frequency bands, with a tree of [`Crossover`]s,
+/// such that the bands sum back to the input passed through an allpass filter.
+///
+/// The input is split at the lowest crossover frequency, the high band is then split at the
+/// next one, and so on. Each band is then passed through the allpass filters of the
+/// crossovers above it, which the higher bands have gone through (as the sum of the two
+/// bands of a crossover), so that all bands are phase-aligned when summed.
+///
+/// Storage is reserved for `BANDS` crossovers (and `BANDS^2` allpass filters),
+/// of which only `BANDS - 1` (and `(BANDS - 1) * (BANDS - 2) / 2`) are used.
+#[derive(Clone, Copy)]
+pub struct MultibandSplitter<const BANDS: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    crossovers: [Crossover<N>; BANDS],
+    allpasses: [[SVF<N>; BANDS]; BANDS],
+    g: [VFloat<N>; BANDS],
+}
+
+impl<const BANDS: usize, const N: usize> MultibandSplitter<BANDS, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a splitter with the given crossover frequencies, see [`Self::set_crossovers`]
+    #[inline]
+    pub fn new(freqs_hz: &[VFloat<N>], sample_rate: f32) -> Self {
+        assert_ne!(BANDS, 0, "a splitter must produce at least one band");
+        let mut this = Self {
+            crossovers: [Crossover::default(); BANDS],
+            allpasses: [[SVF::default(); BANDS]; BANDS],
+            g: [Simd::splat(0.); BANDS],
+        };
+        this.set_crossovers(freqs_hz, sample_rate);
+        this
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        for crossover in &mut self.crossovers {
+            crossover.reset();
+        }
+        for svf in self.allpasses.iter_mut().flatten() {
+            svf.reset();
+        }
+    }
+
+    /// Set the crossover frequencies, in Hz, in increasing order
+    ///
+    /// # Panics
+    ///
+    /// If `freqs_hz.len() != BANDS - 1`
+    #[inline]
+    pub fn set_crossovers(&mut self, freqs_hz: &[VFloat<N>], sample_rate: f32) {
+        assert_eq!(
+            freqs_hz.len(),
+            BANDS - 1,
+            "a splitter producing `BANDS` bands has `BANDS - 1` crossovers"
+        );
+        for (g, &freq) in self.g.iter_mut().zip(freqs_hz) {
+            *g = g_from_hz(freq, sample_rate);
+        }
+    }
+
+    /// Process the input sample `x`, and return the bands, from lowest to highest
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>) -> [VFloat<N>; BANDS] {
+        let res = Simd::splat(SQRT_2);
+        let mut bands = [Simd::splat(0.); BANDS];
+        let mut rest = x;
+
+        let (last, lower) = bands.split_last_mut().unwrap();
+        let g = &self.g[..BANDS - 1];
+
+        for (i, band) in lower.iter_mut().enumerate() {
+            let (mut low, high) = self.crossovers[i].process(rest, g[i]);
+
+            for (allpass, &g) in self.allpasses[i][i + 1..].iter_mut().zip(&g[i + 1..]) {
+                allpass.process(low, g, res);
+                // the sum of the bands of a crossover is the opposite of the SVF's allpass
+                low = -allpass.get_allpass();
+            }
+
+            *band = low;
+            rest = high;
+        }
+
+        *last = rest;
+
+        bands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest difference between `a` and `b`, over all samples and lanes
+    fn max_error(a: &[VFloat<4>], b: &[VFloat<4>]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - b).abs().reduce_max())
+            .fold(0., f32::max)
+    }
+
+    #[test]
+    fn crossover_bands_sum_to_allpassed_input() {
+        let g = Simd::from_array([0.01, 0.1, 1., 10.]);
+        let input = test_noise::<4>(1, 4096);
+
+        let mut crossover = Crossover::default();
+        let mut allpass = SVF::default();
+        let (sum, expected): (Vec<_>, Vec<_>) = input
+            .iter()
+            .map(|&x| {
+                let (low, high) = crossover.process(x, g);
+                allpass.process(x, g, Simd::splat(SQRT_2));
+                (low + high, -allpass.get_allpass())
+            })
+            .unzip();
+
+        let error = max_error(&sum, &expected);
+        assert!(error < 1e-5, "error: {error}");
+    }
+
+    #[test]
+    fn crossover_bands_sum_to_flat_magnitude() {
+        let g = Simd::from_array([0.01, 0.1, 1., 10.]);
+
+        let mut crossover = Crossover::default();
+        let ir: Vec<_> = (0..8192)
+            .map(|i| {
+                let x = Simd::splat(if i == 0 { 1. } else { 0. });
+                let (low, high) = crossover.process(x, g);
+                low + high
+            })
+            .collect();
+
+        // from 10 Hz up to nyquist, at 48 kHz
+        for i in 0..=100 {
+            let omega = core::f32::consts::PI * (2400f32.powf(i as f32 / 100.) / 2400.);
+            let magnitude = dft_magnitude(&ir, Simd::splat(omega));
+            let error = (magnitude - Simd::splat(1.)).abs().reduce_max();
+            assert!(
+                error < 1e-4,
+                "magnitude: {magnitude:?} at {omega} rad/sample"
+            );
+        }
+    }
+
+    #[test]
+    fn multiband_bands_sum_to_allpassed_input() {
+        let sample_rate = 48000.;
+        let freqs_hz = [100., 1000., 5000.].map(Simd::splat);
+        let input = test_noise::<4>(2, 4096);
+
+        let mut splitter = MultibandSplitter::<4, 4>::new(&freqs_hz, sample_rate);
+        // the allpasses of every crossover, in series
+        let mut allpasses = [SVF::default(); 3];
+        let (sum, expected): (Vec<_>, Vec<_>) = input
+            .iter()
+            .map(|&x| {
+                let sum = splitter.process(x).into_iter().sum::<VFloat<4>>();
+                let y = allpasses
+                    .iter_mut()
+                    .zip(&freqs_hz)
+                    .fold(x, |x, (svf, &freq)| {
+                        svf.process(x, g_from_hz(freq, sample_rate), Simd::splat(SQRT_2));
+                        -svf.get_allpass()
+                    });
+                (sum, y)
+            })
+            .unzip();
+
+        let error = max_error(&sum, &expected);
+        assert!(error < 1e-5, "error: {error}");
+    }
+}
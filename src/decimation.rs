@@ -0,0 +1,150 @@
+use super::*;
+
+use svf::{butterworth_sections, cascade_coeffs, SvfCoeffs, SVF};
+
+/// Number of [`SVF`] sections of a [`DecimationFilter`]'s lowpass
+const SECTIONS: usize = 6;
+
+/// Anti-aliasing lowpass filter and decimator, for sample rate conversion by an integer
+/// factor: outputs one sample for every `FACTOR` input samples.
+///
+/// The lowpass is a 12th order Butterworth filter (a cascade of `6` [`SVF`]s), with its
+/// cutoff (`-3 dB`) at `0.8` times the output's nyquist frequency, so that the frequencies
+/// folding back below the cutoff (i.e. above `1.2` times the output's nyquist
+/// frequency) are attenuated by at least `42 dB`, and more, the farther from the cutoff
+/// they fold. It is an IIR filter, so it isn't linear phase, and has to process every input
+/// sample, even those that are then dropped.
+#[derive(Clone, Copy)]
+pub struct DecimationFilter<const FACTOR: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svfs: [SVF<N>; SECTIONS],
+    coeffs: [SvfCoeffs<N>; SECTIONS],
+    phase: usize,
+}
+
+impl<const FACTOR: usize, const N: usize> Default for DecimationFilter<FACTOR, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const FACTOR: usize, const N: usize> DecimationFilter<FACTOR, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new() -> Self {
+        assert_ne!(FACTOR, 0, "the decimation factor must be non-zero");
+
+        // the response only depends on the cutoff relative to the sample rate
+        let cutoff = 0.8 * 0.5 / FACTOR as f32;
+
+        Self {
+            svfs: [SVF::default(); SECTIONS],
+            coeffs: cascade_coeffs(Simd::splat(cutoff), 1., &butterworth_sections()),
+            phase: 0,
+        }
+    }
+
+    /// Reset the filter's state, and restart the decimation phase, so
+    /// that the next output is produced after `FACTOR` input samples
+    #[inline]
+    pub fn reset(&mut self) {
+        self.svfs.iter_mut().for_each(SVF::reset);
+        self.phase = 0;
+    }
+
+    /// Feed one input sample, and return `Some` output sample if `x` completes a group of
+    /// `FACTOR` input samples (i.e. once every `FACTOR` calls), and `None` otherwise
+    #[inline]
+    pub fn push(&mut self, x: VFloat<N>) -> Option<VFloat<N>> {
+        let y = self
+            .svfs
+            .iter_mut()
+            .zip(&self.coeffs)
+            .fold(x, |x, (svf, coeffs)| {
+                svf.process(x, coeffs.g, coeffs.res);
+                *svf.get_lowpass()
+            });
+
+        self.phase += 1;
+        (self.phase == FACTOR).then(|| {
+            self.phase = 0;
+            y
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::f32::consts::TAU;
+
+    /// Impulse response of the anti-aliasing lowpass, at the input sample rate, along with
+    /// that of the whole decimator
+    fn impulse_responses<const FACTOR: usize>(len: usize) -> (Vec<VFloat<1>>, Vec<VFloat<1>>) {
+        let mut filter = DecimationFilter::<FACTOR, 1>::new();
+        let mut decimated = Vec::new();
+
+        let lowpass = (0..len)
+            .map(|i| {
+                let x = Simd::splat(if i == 0 { 1. } else { 0. });
+                decimated.extend(filter.push(x));
+                *filter.svfs[SECTIONS - 1].get_lowpass()
+            })
+            .collect();
+
+        (lowpass, decimated)
+    }
+
+    /// Gain (in dB) of the anti-aliasing lowpass at `freq`, in cycles per input sample
+    fn lowpass_gain_db(ir: &[VFloat<1>], freq: f32) -> f32 {
+        20. * dft_magnitude(ir, Simd::splat(TAU * freq))[0].log10()
+    }
+
+    fn check_anti_aliasing<const FACTOR: usize>() {
+        let (ir, _) = impulse_responses::<FACTOR>(8192);
+        let output_nyquist = 0.5 / FACTOR as f32;
+
+        let cutoff_db = lowpass_gain_db(&ir, 0.8 * output_nyquist);
+        assert!(
+            (cutoff_db + 3.0103).abs() < 1e-3,
+            "FACTOR = {FACTOR}: {cutoff_db} dB at the cutoff"
+        );
+
+        // everything folding back below the cutoff
+        for i in 0..=100 {
+            let freq = 1.2 * output_nyquist + (0.5 - 1.2 * output_nyquist) * i as f32 / 100.;
+            let gain_db = lowpass_gain_db(&ir, freq);
+            assert!(
+                gain_db < -42.,
+                "FACTOR = {FACTOR}: {gain_db} dB at {freq} cycles/sample"
+            );
+        }
+    }
+
+    #[test]
+    fn lowpass_is_3db_down_at_cutoff_and_attenuates_aliases() {
+        check_anti_aliasing::<2>();
+        check_anti_aliasing::<3>();
+        check_anti_aliasing::<4>();
+        check_anti_aliasing::<8>();
+    }
+
+    #[test]
+    fn outputs_every_factor_th_filtered_sample() {
+        let (lowpass, decimated) = impulse_responses::<3>(300);
+
+        assert_eq!(decimated.len(), 100);
+        for (y, expected) in decimated.iter().zip(lowpass.iter().skip(2).step_by(3)) {
+            assert_eq!(y.to_bits(), expected.to_bits());
+        }
+    }
+}
@@ -0,0 +1,514 @@
+use super::*;
+
+use core::f32::consts::{FRAC_1_SQRT_2, PI, TAU};
+
+use envelope::EnvelopeFollower;
+use one_pole::{theta, OnePole};
+use svf::{g_from_hz, res_from_q, FilterShape, SvfCoeffs, SVF};
+
+/// Envelope-controlled bandpass filter (auto-wah).
+///
+/// The input's envelope, scaled by a sensitivity amount, sweeps the cutoff of an
+/// SVF bandpass upwards from a base cutoff frequency.
+#[derive(Clone, Copy)]
+pub struct AutoWah<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    env: EnvelopeFollower<N>,
+    svf: SVF<N>,
+    base_cutoff: VFloat<N>,
+    attack: VFloat<N>,
+    release: VFloat<N>,
+}
+
+impl<const N: usize> AutoWah<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// `base_cutoff` is the cutoff frequency, in Hz, of the bandpass when the envelope is silent
+    ///
+    /// `attack` and `release` are the envelope follower's filtering factors,
+    /// see [`EnvelopeFollower::process`]
+    #[inline]
+    pub fn new(base_cutoff: VFloat<N>, attack: VFloat<N>, release: VFloat<N>) -> Self {
+        Self {
+            env: EnvelopeFollower::default(),
+            svf: SVF::default(),
+            base_cutoff,
+            attack,
+            release,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.env.reset();
+        self.svf.reset();
+    }
+
+    #[inline]
+    pub fn set_base_cutoff(&mut self, base_cutoff: VFloat<N>) {
+        self.base_cutoff = base_cutoff;
+    }
+
+    #[inline]
+    pub fn set_envelope_times(&mut self, attack: VFloat<N>, release: VFloat<N>) {
+        self.attack = attack;
+        self.release = release;
+    }
+
+    /// Process the input sample `x` and return the output of the wah.
+    ///
+    /// `sensitivity` scales the envelope before it is mapped to the cutoff. A scaled envelope
+    /// of `1.0` (or above) opens the filter all the way. `range` is the full sweep width,
+    /// in octaves, above the base cutoff frequency.
+    ///
+    /// `q` is the bandpass' quality factor, and must be strictly positive.
+    #[inline]
+    pub fn process(
+        &mut self,
+        x: VFloat<N>,
+        sensitivity: VFloat<N>,
+        range: VFloat<N>,
+        q: VFloat<N>,
+        sample_rate: f32,
+    ) -> VFloat<N> {
+        let env = self.env.process(x, self.attack, self.release);
+        let amount = (env * sensitivity).simd_clamp(Simd::splat(0.), Simd::splat(1.));
+        let cutoff = self.base_cutoff * (amount * range).exp2();
+
+        self.svf
+            .process(x, g_from_hz(cutoff, sample_rate), res_from_q(q));
+        *self.svf.get_unit_bandpass()
+    }
+
+    /// Get the current envelope value
+    #[inline]
+    pub fn get_envelope(&self) -> &VFloat<N> {
+        self.env.get_envelope()
+    }
+}
+
+/// Simple "body" resonance model: a bank of parallel SVF bandpasses, each with its own
+/// frequency, quality factor and peak gain, along with a short feedback comb filter
+/// and a dry path, all summed together.
+///
+/// `R` is the number of bandpass resonances, and the comb filter's delay is
+/// at most `COMB_LEN - 1` samples long.
+#[derive(Clone, Copy)]
+pub struct Resonator<
+    const R: usize,
+    const N: usize = FLOATS_PER_VECTOR,
+    const COMB_LEN: usize = 512,
+> where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svfs: [SVF<N>; R],
+    coeffs: [SvfCoeffs<N>; R],
+    gains: [VFloat<N>; R],
+    comb: [VFloat<N>; COMB_LEN],
+    comb_pos: usize,
+    comb_delay: usize,
+    comb_feedback: VFloat<N>,
+    comb_gain: VFloat<N>,
+    dry_gain: VFloat<N>,
+}
+
+impl<const R: usize, const N: usize, const COMB_LEN: usize> Default for Resonator<R, N, COMB_LEN>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        assert_ne!(
+            COMB_LEN, 0,
+            "the comb filter's delay line must hold at least one sample"
+        );
+        Self {
+            svfs: [SVF::default(); R],
+            coeffs: [SvfCoeffs::default(); R],
+            gains: [Simd::splat(0.); R],
+            comb: [Simd::splat(0.); COMB_LEN],
+            comb_pos: 0,
+            comb_delay: 1,
+            comb_feedback: Simd::splat(0.),
+            comb_gain: Simd::splat(0.),
+            dry_gain: Simd::splat(1.),
+        }
+    }
+}
+
+impl<const R: usize, const N: usize, const COMB_LEN: usize> Resonator<R, N, COMB_LEN>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        for svf in &mut self.svfs {
+            svf.reset();
+        }
+        self.comb = [Simd::splat(0.); COMB_LEN];
+    }
+
+    /// Set the center frequencies (in Hz), quality factors and peak gains (linear)
+    /// of the bandpass resonances
+    #[inline]
+    pub fn set_resonances(
+        &mut self,
+        freqs_hz: &[VFloat<N>; R],
+        qs: &[VFloat<N>; R],
+        gains: &[VFloat<N>; R],
+        sample_rate: f32,
+    ) {
+        for ((coeffs, &freq), &q) in self.coeffs.iter_mut().zip(freqs_hz).zip(qs) {
+            *coeffs = SvfCoeffs::new(g_from_hz(freq, sample_rate), res_from_q(q));
+        }
+        self.gains = *gains;
+    }
+
+    /// Set the comb filter's delay (in samples, clamped to `[1 ; COMB_LEN - 1]`), feedback
+    /// amount (which must be in `]-1 ; 1[` for the comb to be stable), and output gain
+    #[inline]
+    pub fn set_comb(&mut self, delay: usize, feedback: VFloat<N>, gain: VFloat<N>) {
+        self.comb_delay = delay.clamp(1, COMB_LEN.max(2) - 1);
+        self.comb_feedback = feedback;
+        self.comb_gain = gain;
+    }
+
+    #[inline]
+    pub fn set_dry_gain(&mut self, gain: VFloat<N>) {
+        self.dry_gain = gain;
+    }
+
+    /// Process the input sample `x` and return the sum of the dry signal,
+    /// the comb filter's output, and each of the resonances
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>) -> VFloat<N> {
+        // y[n] = x[n - D] + feedback * y[n - D]
+        let delayed = self.comb[(self.comb_pos + COMB_LEN - self.comb_delay) % COMB_LEN];
+        self.comb[self.comb_pos] = self.comb_feedback.mul_add(delayed, x);
+        self.comb_pos = (self.comb_pos + 1) % COMB_LEN;
+
+        let mut out = self.dry_gain.mul_add(x, self.comb_gain * delayed);
+
+        for ((svf, coeffs), &gain) in self.svfs.iter_mut().zip(&self.coeffs).zip(&self.gains) {
+            svf.process(x, coeffs.g, coeffs.res);
+            out = gain.mul_add(*svf.get_unit_bandpass(), out);
+        }
+
+        out
+    }
+}
+
+/// The vowels available to the [`Formant`] filter, in the order they are interpolated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vowel {
+    A,
+    E,
+    I,
+    O,
+    U,
+}
+
+impl Vowel {
+    pub const ALL: [Self; 5] = [Self::A, Self::E, Self::I, Self::O, Self::U];
+
+    /// The center frequencies (in Hz), quality factors and peak gains (linear) of the first
+    /// three formants of this vowel, for an adult male (bass) voice
+    #[inline]
+    pub fn formants(self) -> [(f32, f32, f32); 3] {
+        // frequencies, bandwidths (Hz) and levels (dB) from the classic bass voice table,
+        // with Q = frequency / bandwidth, and levels converted to linear gains
+        match self {
+            Self::A => [
+                (600., 10., 1.),
+                (1040., 14.857, 0.446_684),
+                (2250., 20.455, 0.354_813),
+            ],
+            Self::E => [
+                (400., 10., 1.),
+                (1620., 20.25, 0.251_189),
+                (2400., 24., 0.354_813),
+            ],
+            Self::I => [
+                (250., 4.167, 1.),
+                (1750., 19.444, 0.031_623),
+                (2600., 26., 0.158_489),
+            ],
+            Self::O => [
+                (400., 10., 1.),
+                (750., 9.375, 0.281_838),
+                (2400., 24., 0.089_125),
+            ],
+            Self::U => [(350., 8.75, 1.), (600., 7.5, 0.1), (2400., 24., 0.025_119)],
+        }
+    }
+}
+
+/// Vowel formant filter, made of three parallel SVF bandpasses, one per formant,
+/// whose settings are interpolated between the [`Vowel`] presets.
+#[derive(Default, Clone, Copy)]
+pub struct Formant<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svfs: [SVF<N>; 3],
+}
+
+impl<const N: usize> Formant<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        for svf in &mut self.svfs {
+            svf.reset();
+        }
+    }
+
+    /// Process the input sample `x`, and return the sum of the three formants.
+    ///
+    /// `vowel` is clamped to `[0 ; 1]` and sweeps, with linear interpolation of the formants'
+    /// frequencies, Qs and gains, through `A`, `E`, `I`, `O` and `U`, which sit at
+    /// `0`, `0.25`, `0.5`, `0.75` and `1` respectively.
+    ///
+    /// The coefficients are recomputed (which involves a `tan` per formant) on every call.
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, vowel: VFloat<N>, sample_rate: f32) -> VFloat<N> {
+        let mut freqs = [[0.; N]; 3];
+        let mut qs = [[0.; N]; 3];
+        let mut gains = [[0.; N]; 3];
+
+        for (lane, vowel) in vowel.to_array().into_iter().enumerate() {
+            let pos = vowel.clamp(0., 1.) * (Vowel::ALL.len() - 1) as f32;
+            let i = (pos as usize).min(Vowel::ALL.len() - 2);
+            let t = pos - i as f32;
+
+            let from = Vowel::ALL[i].formants();
+            let to = Vowel::ALL[i + 1].formants();
+
+            for (k, (&(f0, q0, a0), &(f1, q1, a1))) in from.iter().zip(&to).enumerate() {
+                freqs[k][lane] = t.mul_add(f1 - f0, f0);
+                qs[k][lane] = t.mul_add(q1 - q0, q0);
+                gains[k][lane] = t.mul_add(a1 - a0, a0);
+            }
+        }
+
+        let mut out = Simd::splat(0.);
+
+        for (k, svf) in self.svfs.iter_mut().enumerate() {
+            let g = g_from_hz(Simd::from_array(freqs[k]), sample_rate);
+            svf.process(x, g, res_from_q(Simd::from_array(qs[k])));
+            out = Simd::from_array(gains[k]).mul_add(*svf.get_unit_bandpass(), out);
+        }
+
+        out
+    }
+}
+
+/// Number of allpass stages per channel of the [`Diffuser`]
+const DIFFUSER_STAGES: usize = 4;
+
+/// Allpass corner frequencies, in Hz, of the [`Diffuser`]'s stages, for each channel,
+/// spread out and mutually detuned, to decorrelate the channels
+const DIFFUSER_FREQS: [[f32; DIFFUSER_STAGES]; 2] =
+    [[150., 530., 1700., 4300.], [190., 610., 2100., 5100.]];
+
+/// Stereo diffusion stage, a building block for simple Schroeder or FDN style reverbs.
+///
+/// Each channel goes through a chain of one-pole allpass filters, whose outputs, delayed
+/// by one sample, are fed back into both channels' inputs, through an orthogonal (rotation)
+/// mixing matrix, scaled by the diffusion coefficient.
+///
+/// The allpass chains preserve energy, and so does the rotation, thus, the feedback loop's
+/// gain is exactly `|diffusion|`: the diffuser is stable as long as `|diffusion| < 1`.
+/// The closer it is to `1`, the longer and denser the tail.
+#[derive(Clone, Copy)]
+pub struct Diffuser<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    stages: [[OnePole<N>; DIFFUSER_STAGES]; 2],
+    thetas: [[VFloat<N>; DIFFUSER_STAGES]; 2],
+    feedback: [VFloat<N>; 2],
+    diffusion: VFloat<N>,
+}
+
+impl<const N: usize> Diffuser<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a diffuser with the given diffusion coefficient, see [`Self::set_diffusion`]
+    #[inline]
+    pub fn new(diffusion: VFloat<N>, sample_rate: f32) -> Self {
+        Self {
+            stages: [[OnePole::default(); DIFFUSER_STAGES]; 2],
+            thetas: DIFFUSER_FREQS.map(|freqs| {
+                freqs.map(|freq| theta(Simd::splat((TAU * freq / sample_rate).min(0.999 * PI))))
+            }),
+            feedback: [Simd::splat(0.); 2],
+            diffusion,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        for stage in self.stages.iter_mut().flatten() {
+            stage.reset();
+        }
+        self.feedback = [Simd::splat(0.); 2];
+    }
+
+    /// Set the diffusion coefficient, i.e. the feedback gain, which must be in `]-1 ; 1[`
+    #[inline]
+    pub fn set_diffusion(&mut self, diffusion: VFloat<N>) {
+        self.diffusion = diffusion;
+    }
+
+    /// Process the left and right input samples, and return the diffused left and right samples
+    #[inline]
+    pub fn process(&mut self, l: VFloat<N>, r: VFloat<N>) -> (VFloat<N>, VFloat<N>) {
+        // 45 degree rotation
+        let k = self.diffusion * Simd::splat(FRAC_1_SQRT_2);
+        let [fl, fr] = self.feedback;
+        let inputs = [k.mul_add(fl - fr, l), k.mul_add(fl + fr, r)];
+
+        let mut outputs = inputs;
+        for ((y, stages), thetas) in outputs.iter_mut().zip(&mut self.stages).zip(&self.thetas) {
+            for (stage, &theta) in stages.iter_mut().zip(thetas) {
+                stage.process(*y, theta);
+                *y = stage.get_allpass();
+            }
+        }
+
+        self.feedback = outputs;
+        let [l, r] = outputs;
+        (l, r)
+    }
+}
+
+/// Chain of `STAGES` one-pole allpass filters in series, sharing the same cutoff, with
+/// optional feedback from the last stage to the first, the core of a phaser.
+///
+/// Mixing the (wet) output with the dry input, in equal parts, produces `STAGES / 2` notches,
+/// swept by modulating the cutoff. The feedback, delayed by one sample, makes the response
+/// resonant, emphasizing the peaks between the notches, with a sharper, more pronounced
+/// sound. The chain preserves energy, so the loop is stable as long as `|feedback| < 1`.
+#[derive(Clone, Copy)]
+pub struct AllpassChain<const STAGES: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    stages: [OnePole<N>; STAGES],
+    last: VFloat<N>,
+}
+
+impl<const STAGES: usize, const N: usize> Default for AllpassChain<STAGES, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stages: [OnePole::default(); STAGES],
+            last: Simd::splat(0.),
+        }
+    }
+}
+
+impl<const STAGES: usize, const N: usize> AllpassChain<STAGES, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.stages.iter_mut().for_each(OnePole::reset);
+        self.last = Simd::splat(0.);
+    }
+
+    /// Process the input sample `x`, and return the output of the last stage.
+    ///
+    /// `theta` is the filtering factor (see [`OnePole::process`]) of every stage, and
+    /// `feedback`, in `]-1 ; 1[`, the gain applied to the previous output before it's added
+    /// to the input.
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, theta: VFloat<N>, feedback: VFloat<N>) -> VFloat<N> {
+        let input = feedback.mul_add(self.last, x);
+
+        self.last = self.stages.iter_mut().fold(input, |y, stage| {
+            stage.process(y, theta);
+            stage.get_allpass()
+        });
+
+        self.last
+    }
+
+    /// Get the output of the last stage
+    #[inline]
+    pub fn get_output(&self) -> &VFloat<N> {
+        &self.last
+    }
+}
+
+/// Mid/side filter: pair of [`SVF`]s, filtering the mid and side signals of a stereo input
+/// independently, e.g. for mid/side equalization.
+///
+/// The encoding convention is `mid = (l + r) / 2` and `side = (l - r) / 2`, decoded
+/// as `l = mid + side` and `r = mid - side`, so that, with identical settings for both
+/// filters, this is the same as filtering `l` and `r` directly.
+///
+/// Each of the `N` lanes holds a separate stereo signal (e.g. a voice). Mid and side
+/// don't share vectors, both being full-width, to keep every lane of both filters in use.
+#[derive(Default, Clone, Copy)]
+pub struct MidSide<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    mid: SVF<N>,
+    side: SVF<N>,
+}
+
+impl<const N: usize> MidSide<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.mid.reset();
+        self.side.reset();
+    }
+
+    /// Encode the left and right samples `l` and `r` to mid/side, filter them with their
+    /// respective coefficients, take the output selected by `shape` for both, and decode
+    /// the results back to left and right
+    #[inline]
+    pub fn process_midside(
+        &mut self,
+        l: VFloat<N>,
+        r: VFloat<N>,
+        mid_coeffs: &SvfCoeffs<N>,
+        side_coeffs: &SvfCoeffs<N>,
+        shape: FilterShape<VFloat<N>>,
+    ) -> (VFloat<N>, VFloat<N>) {
+        let half = Simd::splat(0.5);
+
+        Filter::process(&mut self.mid, (l + r) * half, mid_coeffs);
+        Filter::process(&mut self.side, (l - r) * half, side_coeffs);
+
+        let mid = self.mid.get(shape);
+        let side = self.side.get(shape);
+
+        (mid + side, mid - side)
+    }
+
+    #[inline]
+    pub fn mid(&self) -> &SVF<N> {
+        &self.mid
+    }
+
+    #[inline]
+    pub fn side(&self) -> &SVF<N> {
+        &self.side
+    }
+}
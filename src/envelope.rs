@@ -0,0 +1,122 @@
+use super::*;
+
+use one_pole::OnePole;
+use simd_util::simd::{cmp::SimdPartialEq, num::SimdUint};
+
+/// Peak envelope follower, built on top of a one-pole lowpass filter.
+///
+/// The input is rectified (`|x|`), then smoothed by the lowpass, using a different
+/// "filtering factor" depending on whether the envelope is rising (attack) or
+/// falling (release). The choice is made independently in each lane.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct EnvelopeFollower<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    lp: OnePole<N>,
+}
+
+impl<const N: usize> EnvelopeFollower<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.lp.reset();
+    }
+
+    /// Feed the input sample `x`, and return the updated envelope.
+    ///
+    /// `attack` and `release` are the one-pole filtering factors (`theta`) used when the
+    /// rectified input is, respectively, above or below the current envelope.
+    /// See [`one_pole::theta`].
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, attack: VFloat<N>, release: VFloat<N>) -> VFloat<N> {
+        let rect = x.abs();
+        let rising = rect.simd_gt(*self.lp.get_lowpass());
+        self.lp.process(rect, rising.select(attack, release));
+        *self.lp.get_lowpass()
+    }
+
+    /// Get the current envelope value
+    #[inline]
+    pub fn get_envelope(&self) -> &VFloat<N> {
+        self.lp.get_lowpass()
+    }
+}
+
+/// Smoother for gain-reduction values, built on top of a one-pole lowpass filter.
+///
+/// The gain moves towards its target using the `attack` filtering factor when falling
+/// (i.e. when more gain reduction is applied) and the `release` one when rising. After
+/// the target falls, the lowest target value is held for `hold` samples before release begins.
+///
+/// The smoothed gain starts at unity.
+#[derive(Clone, Copy, PartialEq)]
+pub struct GainSmoother<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    lp: OnePole<N>,
+    held: VFloat<N>,
+    countdown: Simd<u32, N>,
+    attack: VFloat<N>,
+    release: VFloat<N>,
+    hold: u32,
+}
+
+impl<const N: usize> GainSmoother<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// `attack` and `release` are one-pole filtering factors (see [`one_pole::theta`]),
+    /// `hold` is a number of samples.
+    #[inline]
+    pub fn new(attack: VFloat<N>, release: VFloat<N>, hold: u32) -> Self {
+        let mut lp = OnePole::default();
+        lp.reset_to(Simd::splat(1.));
+        Self {
+            lp,
+            held: Simd::splat(1.),
+            countdown: Simd::splat(0),
+            attack,
+            release,
+            hold,
+        }
+    }
+
+    /// Set the smoothed gain back to unity, cancelling any ongoing hold
+    #[inline]
+    pub fn reset(&mut self) {
+        self.lp.reset_to(Simd::splat(1.));
+        self.held = Simd::splat(1.);
+        self.countdown = Simd::splat(0);
+    }
+
+    #[inline]
+    pub fn set_times(&mut self, attack: VFloat<N>, release: VFloat<N>, hold: u32) {
+        self.attack = attack;
+        self.release = release;
+        self.hold = hold;
+    }
+
+    /// Feed the gain value the smoother should move towards and return the smoothed gain
+    #[inline]
+    pub fn process(&mut self, target_gain: VFloat<N>) -> VFloat<N> {
+        let falling = target_gain.simd_le(self.held);
+        let holding = self.countdown.simd_ne(Simd::splat(0));
+
+        // a new minimum restarts the hold, otherwise, stick to the
+        // held value until the countdown elapses, then follow the target
+        self.countdown = falling.select(
+            Simd::splat(self.hold),
+            self.countdown.saturating_sub(Simd::splat(1)),
+        );
+        self.held = (falling | !holding).select(target_gain, self.held);
+
+        let attacking = self.held.simd_lt(*self.lp.get_lowpass());
+        self.lp
+            .process(self.held, attacking.select(self.attack, self.release));
+        *self.lp.get_lowpass()
+    }
+}
@@ -0,0 +1,417 @@
+use super::*;
+
+use core::f32::consts::SQRT_2;
+
+use svf::{g_from_hz, res_from_q, FilterShape, SvfCoeffs, SVF};
+
+/// Plain description of a filter's design, independent of any internal SIMD state
+/// or coefficient representation. Meant for storing or exchanging filter settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterDesign {
+    /// Which output of the filter is used. The gain is given by `gain_db` instead.
+    pub shape: FilterShape<()>,
+    /// Cutoff (or center) frequency in Hz
+    pub freq_hz: f32,
+    /// Quality factor, must be strictly positive
+    pub q: f32,
+    /// Gain of the shelving shapes, in decibels. Ignored by the other shapes
+    pub gain_db: f32,
+    pub sample_rate: f32,
+}
+
+impl FilterDesign {
+    /// Compute the coefficients and runtime shape realizing this design with an [`SVF`]
+    #[inline]
+    pub fn to_runtime<const N: usize>(&self) -> (SvfCoeffs<N>, FilterShape<VFloat<N>>)
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        let coeffs = SvfCoeffs::new(
+            g_from_hz(Simd::splat(self.freq_hz), self.sample_rate),
+            res_from_q(Simd::splat(self.q)),
+        );
+
+        // the low/high shelf getters take the square root of the linear gain
+        let gain = |db: f32| Simd::splat(10f32.powf(db / 20.));
+        let root_gain = |db: f32| gain(db * 0.5);
+
+        let shape = match self.shape {
+            FilterShape::Passthrough => FilterShape::Passthrough,
+            FilterShape::Lowpass => FilterShape::Lowpass,
+            FilterShape::Bandpass => FilterShape::Bandpass,
+            FilterShape::UnitBandpass => FilterShape::UnitBandpass,
+            FilterShape::Highpass => FilterShape::Highpass,
+            FilterShape::Allpass => FilterShape::Allpass,
+            FilterShape::Notch => FilterShape::Notch,
+            FilterShape::LowShelf(()) => FilterShape::LowShelf(root_gain(self.gain_db)),
+            FilterShape::BandShelf(()) => FilterShape::BandShelf(gain(self.gain_db)),
+            FilterShape::HighShelf(()) => FilterShape::HighShelf(root_gain(self.gain_db)),
+        };
+
+        (coeffs, shape)
+    }
+}
+
+/// Makeup gain applied to each shape by [`EqBand`]'s normalized mode, so that switching between
+/// shapes at the same cutoff keeps the loudness of broadband material roughly constant.
+///
+/// Derived from the analog prototypes (which the digital filters match well below nyquist),
+/// as the inverse RMS gain of each shape over pink noise spanning 5 octaves on either side of
+/// the cutoff, for a Butterworth resonance (`q = 1/sqrt(2)`), i.e. `1 / sqrt(P)` where:
+///
+/// `P = (1/(10 ln 2)) * integral of |H(jw)|^2 dw/w, for w in [2^-5 ; 2^5]`
+///
+/// By symmetry, the lowpass and highpass (and allpass and passthrough) get the same gain.
+/// The values are only indicative for other resonances. The shelving shapes are left
+/// untouched, as their change in loudness is intended.
+#[inline]
+pub fn normalization_gain<G>(shape: FilterShape<G>) -> f32 {
+    match shape {
+        FilterShape::Lowpass | FilterShape::Highpass => SQRT_2, // +3.01 dB
+        FilterShape::Bandpass => 2.972_625,                     // +9.46 dB
+        FilterShape::UnitBandpass => 2.101_963,                 // +6.45 dB
+        FilterShape::Notch => 1.136_903,                        // +1.11 dB
+        FilterShape::Passthrough
+        | FilterShape::Allpass
+        | FilterShape::LowShelf(_)
+        | FilterShape::BandShelf(_)
+        | FilterShape::HighShelf(_) => 1.,
+    }
+}
+
+/// Default weighting curve used by [`perceptual_shelf_gain`]: the rate, relative to that at
+/// `1 kHz`, at which the perceived loudness (in phons) of a tone grows with its level (in dB),
+/// at the frequency `freq_hz`.
+///
+/// Derived from the spacing of the ISO 226 equal-loudness contours between 40 and 100 phons,
+/// which are tightly packed at low frequencies (at `20 Hz`, they span only about 25 dB), and
+/// roughly evenly spaced above `500 Hz`. The curve is interpolated linearly in log-frequency
+/// between a few key points, and is only an approximation, independent of the listening level.
+#[inline]
+pub fn loudness_growth_rate(freq_hz: f32) -> f32 {
+    const POINTS: [(f32, f32); 7] = [
+        (20., 2.4),
+        (50., 1.8),
+        (100., 1.43),
+        (200., 1.2),
+        (500., 1.05),
+        (1000., 1.),
+        (20000., 1.),
+    ];
+
+    let (first, last) = (POINTS[0], POINTS[POINTS.len() - 1]);
+    if freq_hz <= first.0 {
+        return first.1;
+    }
+    if freq_hz >= last.0 {
+        return last.1;
+    }
+
+    let i = POINTS.partition_point(|&(f, _)| f <= freq_hz);
+    let ((f0, r0), (f1, r1)) = (POINTS[i - 1], POINTS[i]);
+    let t = (freq_hz / f0).log2() / (f1 / f0).log2();
+    t.mul_add(r1 - r0, r0)
+}
+
+/// Linear gain to give a shelf (or bell) centered at `center_hz`, in each lane, so that it is
+/// perceived as a change of `db` decibels at `1 kHz` would be, using the default weighting
+/// curve, [`loudness_growth_rate`].
+///
+/// As the perceived loudness grows faster with level at low frequencies, the same dB setting
+/// sounds like a bigger change in the bass, so the applied gain is `db / rate(center_hz)`.
+///
+/// The result is the gain of the shelf's plateau, to be passed as is to the band shelf
+/// getter, and square-rooted for the low and high shelf ones.
+#[inline]
+pub fn perceptual_shelf_gain<const N: usize>(db: VFloat<N>, center_hz: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    perceptual_shelf_gain_with(db, center_hz, loudness_growth_rate)
+}
+
+/// Same as [`perceptual_shelf_gain`], with a custom weighting curve, `rate`, which returns,
+/// given a frequency in Hz, the rate at which perceived loudness grows with level.
+/// It should be `1` wherever `db` is to be applied unchanged.
+#[inline]
+pub fn perceptual_shelf_gain_with<const N: usize>(
+    db: VFloat<N>,
+    center_hz: VFloat<N>,
+    rate: impl Fn(f32) -> f32,
+) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let rates = Simd::from_array(center_hz.to_array().map(rate));
+    let db = db / rates;
+    // 10^(db / 20)
+    (db * Simd::splat(core::f32::consts::LOG2_10 / 20.)).exp2()
+}
+
+/// A single band of an equalizer: an [`SVF`] along with the design it realizes
+#[derive(Clone, Copy)]
+pub struct EqBand<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svf: SVF<N>,
+    design: FilterDesign,
+    coeffs: SvfCoeffs<N>,
+    shape: FilterShape<VFloat<N>>,
+    normalized: bool,
+    makeup_gain: VFloat<N>,
+    makeup: VFloat<N>,
+}
+
+impl<const N: usize> EqBand<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn from_design(design: FilterDesign) -> Self {
+        let (coeffs, shape) = design.to_runtime();
+        Self {
+            svf: SVF::default(),
+            design,
+            coeffs,
+            shape,
+            normalized: false,
+            makeup_gain: Simd::splat(1.),
+            makeup: Simd::splat(1.),
+        }
+    }
+
+    /// Get the design this band currently realizes
+    #[inline]
+    pub fn to_design(&self) -> FilterDesign {
+        self.design
+    }
+
+    /// Change this band's design, keeping the filter's internal state
+    #[inline]
+    pub fn set_design(&mut self, design: FilterDesign) {
+        (self.coeffs, self.shape) = design.to_runtime();
+        self.design = design;
+        self.update_makeup();
+    }
+
+    /// Enable or disable the per-shape loudness normalization, see [`normalization_gain`]
+    #[inline]
+    pub fn set_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+        self.update_makeup();
+    }
+
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Set the gain applied to this band's output, in dB, in each lane, e.g. to compensate
+    /// for the level lost to a highpass. `0 dB` (the default) leaves the output untouched.
+    /// It adds to the normalization gain, if enabled.
+    #[inline]
+    pub fn set_makeup_db(&mut self, db: VFloat<N>) {
+        // 10^(db/20) = 2^(db * log2(10) / 20)
+        self.makeup_gain = (db * Simd::splat(core::f32::consts::LOG2_10 / 20.)).exp2();
+        self.update_makeup();
+    }
+
+    /// The (linear) gain set by [`Self::set_makeup_db`]
+    #[inline]
+    pub fn makeup_gain(&self) -> &VFloat<N> {
+        &self.makeup_gain
+    }
+
+    #[inline]
+    fn update_makeup(&mut self) {
+        let normalization = Simd::splat(if self.normalized {
+            normalization_gain(self.design.shape)
+        } else {
+            1.
+        });
+        self.makeup = normalization * self.makeup_gain;
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.svf.reset();
+    }
+
+    /// Feed the input sample `x` to the band's filter and return its output
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>) -> VFloat<N> {
+        Filter::process(&mut self.svf, x, &self.coeffs);
+        self.svf.get(self.shape) * self.makeup
+    }
+}
+
+/// Resonant peak equalizer: a bump of adjustable height and width, narrower and more
+/// resonance-like than the bell ([`FilterShape::BandShelf`]).
+///
+/// The bell mixes the bandpass back into the signal, which makes for a wide bump whose
+/// skirts approach `0 dB` slowly. Here, the input goes through two identical [`SVF`]s in
+/// series, each one contributing half (in dB) of the peak through its own band shelf output,
+/// see [`svf::trnasfer::resonant_peak_eq`]. The poles are those of the resonance alone, so
+/// the width only depends on `res`, and the height only on the gain. For the same height and
+/// `res`, the bump is narrower, and the skirts closer to flat: e.g. for a `12 dB` peak with
+/// `res = 0.5`, it spans `1` octave at half its height, instead of `1.4` for a single
+/// section's band shelf ([`SVF::get_band_shelf`]).
+/// The gain is exactly `1` at DC and nyquist, and `peak_gain` at the center frequency.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct ResonantPeak<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svfs: [SVF<N>; 2],
+    root_gain: VFloat<N>,
+}
+
+impl<const N: usize> ResonantPeak<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.svfs.iter_mut().for_each(SVF::reset);
+    }
+
+    /// Feed the input sample `x`, with the center frequency set by `g`, and the width by
+    /// `res`, as in [`SVF::process`], and the (linear) height by `peak_gain`
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>, peak_gain: VFloat<N>) {
+        let root_gain = peak_gain.sqrt();
+        let [first, second] = &mut self.svfs;
+        first.process(x, g, res);
+        second.process(first.get_band_shelf(root_gain), g, res);
+        self.root_gain = root_gain;
+    }
+
+    /// Get the output computed by the last `process` call
+    #[inline]
+    pub fn get_resonant_peak(&self) -> VFloat<N> {
+        self.svfs[1].get_band_shelf(self.root_gain)
+    }
+}
+
+/// Fit a bell ([`FilterShape::BandShelf`]) band to a target magnitude response, given as
+/// `(frequency_hz, gain_db)` points, returning the band minimizing the sum of the squared
+/// differences, in dB, between the digital filter's response (see
+/// [`svf::trnasfer::z_response`]) and the targets, at each of their frequencies.
+///
+/// The center frequency, gain, and Q are first searched for on a coarse grid (frequencies
+/// from `10 Hz` to just below nyquist, gains within `+/-30 dB`, and Qs from `0.1` to `20`,
+/// clamped to these ranges throughout), which is then refined with a pattern search, whose
+/// step sizes are halved whenever no neighbouring point improves the fit, until they become
+/// negligible. This is a local search from the best grid point, so targets that a single
+/// bell can't approximate well give a best-effort result.
+///
+/// # Panics
+///
+/// If `targets` is empty
+#[cfg(feature = "num")]
+pub fn fit_bell<const N: usize>(targets: &[(f64, f64)], sample_rate: f32) -> EqBand<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    use core::f64::consts::{PI, TAU};
+
+    assert!(!targets.is_empty(), "at least one target point is required");
+
+    let sr = f64::from(sample_rate);
+
+    // parameters: [log2(freq_hz), gain_db, log2(q)]
+    let ranges = [
+        (10f64.log2(), (0.499 * sr).log2(), 48),
+        (-30., 30., 31),
+        (0.1f64.log2(), 20f64.log2(), 16),
+    ];
+
+    let cost = |[f, gain, q]: [f64; 3]| -> f64 {
+        let shape = FilterShape::BandShelf(10f64.powf(gain / 20.));
+        let g = (PI * f.exp2() / sr).tan();
+        let res = q.exp2().recip();
+        targets
+            .iter()
+            .map(|&(freq, target_db)| {
+                let h = svf::trnasfer::z_response(shape, g, res, TAU * freq / sr);
+                let err = 20. * h.norm().log10() - target_db;
+                err * err
+            })
+            .sum()
+    };
+
+    let (best, _) = grid_pattern_search(ranges, cost);
+
+    let [f, gain, q] = best;
+    EqBand::from_design(FilterDesign {
+        shape: FilterShape::BandShelf(()),
+        freq_hz: f.exp2() as f32,
+        q: q.exp2() as f32,
+        gain_db: gain as f32,
+        sample_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_db_makeup_is_a_no_op() {
+        let design = FilterDesign {
+            shape: FilterShape::Highpass,
+            freq_hz: 500.,
+            q: 0.7,
+            gain_db: 0.,
+            sample_rate: 48000.,
+        };
+
+        for normalized in [false, true] {
+            let mut band = EqBand::<4>::from_design(design);
+            band.set_normalized(normalized);
+            let mut reference = band;
+
+            band.set_makeup_db(Simd::splat(0.));
+            assert_eq!(band.makeup_gain(), &Simd::splat(1.));
+
+            for x in test_noise::<4>(1, 1000) {
+                let (y, expected) = (band.process(x), reference.process(x));
+                assert_eq!(y.to_bits(), expected.to_bits());
+            }
+        }
+    }
+
+    #[cfg(feature = "num")]
+    #[test]
+    fn fit_bell_recovers_known_band() {
+        let sample_rate = 48000.;
+        let design = FilterDesign {
+            shape: FilterShape::BandShelf(()),
+            freq_hz: 1200.,
+            q: 1.5,
+            gain_db: 6.,
+            sample_rate,
+        };
+
+        let (coeffs, shape) = design.to_runtime::<1>();
+        let shape = shape.map(|gain| f64::from(gain[0]));
+        let (g, res) = (f64::from(coeffs.g[0]), f64::from(coeffs.res[0]));
+        let targets: Vec<_> = (0..40)
+            .map(|i| {
+                let freq = 20. * 1000f64.powf(i as f64 / 39.);
+                let omega = core::f64::consts::TAU * freq / f64::from(sample_rate);
+                let h = svf::trnasfer::z_response(shape, g, res, omega);
+                (freq, 20. * h.norm().log10())
+            })
+            .collect();
+
+        let fitted = fit_bell::<1>(&targets, sample_rate).to_design();
+        assert!(
+            (fitted.freq_hz / design.freq_hz - 1.).abs() < 1e-3
+                && (fitted.q / design.q - 1.).abs() < 1e-3
+                && (fitted.gain_db - design.gain_db).abs() < 1e-2,
+            "fitted {fitted:?}, expected {design:?}"
+        );
+    }
+}
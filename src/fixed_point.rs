@@ -0,0 +1,177 @@
+/// Scale of the Q31 format: `1.0` in Q31, i.e. `2^31`
+const Q31_ONE: f64 = (1u64 << 31) as f64;
+
+/// Convert `x`, in `[-1 ; 1[`, to the Q31 fixed-point format (`31` fractional bits),
+/// rounding to the nearest value, and saturating outside of that range (NaNs give `0`)
+#[inline]
+pub fn to_q31(x: f32) -> i32 {
+    // float to int casts saturate, and map NaN to 0
+    (x as f64 * Q31_ONE).round() as i32
+}
+
+/// Convert `x`, in Q31, back to a float
+#[inline]
+pub fn from_q31(x: i32) -> f32 {
+    (x as f64 / Q31_ONE) as f32
+}
+
+/// Fixed-point implementation of the trapezoidal [`Integrator`](crate::Integrator), with the
+/// same difference equations, on `64`-bit integers, in the same fixed-point format as its
+/// input (e.g. Q31). The state isn't bounded by the input's range, hence the headroom,
+/// see [`Q31OnePole`].
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Q31Integrator {
+    s: i64,
+    out: i64,
+}
+
+impl Q31Integrator {
+    /// Feed the input sample `x` (`x[n]`), update the internal state (`v[n]`), and
+    /// compute the next output (`y[n]`), see [`Integrator::process`](crate::Integrator::process)
+    #[inline]
+    pub fn process(&mut self, x: i64) {
+        self.out = x + self.s;
+        self.s = self.out + x;
+    }
+
+    /// Get the current `y[n]` output
+    #[inline]
+    pub fn output(&self) -> i64 {
+        self.out
+    }
+
+    /// Get the current `v[n]` state
+    #[inline]
+    pub fn state(&self) -> i64 {
+        self.s
+    }
+
+    /// Set the internal `v[n]` state, and the cached `y[n]` output, to `0`
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Fixed-point implementation of the one-pole TPT filter, for targets without an FPU,
+/// with the same structure as [`OnePole`](crate::one_pole::OnePole), processing a single
+/// channel of Q31 samples with integer arithmetic only.
+///
+/// The [`Q31Integrator`]'s state is kept on `64` bits, giving it the headroom it needs: for
+/// `theta > 0.5`, it isn't bounded by the input's range, but by `theta / (1 - theta)` times
+/// it (reached at nyquist), at most `2^31` times, since `theta < 1` in Q31. Products are
+/// computed on `128` bits, and rounded to the nearest Q31 value. The outputs aren't bounded
+/// by the input's range either, as the lowpass' (negative) pole makes it overshoot, so they
+/// saturate at full scale. For `theta <= 0.5` (cutoffs up to a quarter of the sample rate),
+/// the lowpass never overshoots, and only the highpass (`x - lp`, up to twice the input's
+/// range) can saturate.
+///
+/// Rounding stalls the state once `theta` times its distance to the input rounds to zero,
+/// i.e. falls below half an LSB, so the lowpass settles within `1 / (2 * theta)` LSBs of a
+/// constant input (e.g. `500` for `theta = 0.001`), which only matters at very low cutoffs.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Q31OnePole {
+    lp: Q31Integrator,
+    x: i32,
+}
+
+impl Q31OnePole {
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Convert the (floating-point) filtering factor `theta` (see [`theta`](crate::one_pole::theta)),
+    /// in `[0 ; 1]`, to Q31, as accepted by [`Self::process`], `1.0` being rounded down to
+    /// the largest Q31 value
+    #[inline]
+    pub fn theta_to_q31(theta: f32) -> i32 {
+        to_q31(theta).max(0)
+    }
+
+    /// Update the filter's internal state, see [`OnePole::process`](crate::one_pole::OnePole::process).
+    ///
+    /// `x` is the input sample, and `theta` the filtering factor, both in Q31, `theta`
+    /// being non-negative, see [`Self::theta_to_q31`]
+    #[inline]
+    pub fn process(&mut self, x: i32, theta: i32) {
+        let d = x as i64 - self.lp.state();
+        // the state can grow way past the input's range (see above), so the product
+        // needs more than 64 bits. Rounded to nearest.
+        let v = ((d as i128 * theta as i128 + (1 << 30)) >> 31) as i64;
+
+        self.lp.process(v);
+        self.x = x;
+    }
+
+    #[inline]
+    pub fn get_passthrough(&self) -> i32 {
+        self.x
+    }
+
+    #[inline]
+    pub fn get_lowpass(&self) -> i32 {
+        self.lp.output().clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    #[inline]
+    pub fn get_highpass(&self) -> i32 {
+        self.x.saturating_sub(self.get_lowpass())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowpass_stalls_within_documented_bound() {
+        for theta in [0.001, 0.01, 0.1] {
+            let mut filter = Q31OnePole::default();
+            let x = to_q31(0.5);
+            for _ in 0..1_000_000 {
+                filter.process(x, Q31OnePole::theta_to_q31(theta));
+            }
+
+            let error = (x - filter.get_lowpass()).abs() as f32;
+            assert!(error <= 0.5 / theta, "theta = {theta}: {error} LSBs");
+        }
+    }
+
+    #[test]
+    fn matches_floating_point_one_pole() {
+        use crate::{one_pole::OnePole, test_noise, Simd};
+
+        for theta in [0.01, 0.2, 0.5, 0.9] {
+            let mut fixed = Q31OnePole::default();
+            let mut float = OnePole::<1>::default();
+            let theta_q31 = Q31OnePole::theta_to_q31(theta);
+
+            // scaled down, so that neither the lowpass' overshoot, nor the highpass, saturate
+            for x in test_noise::<1>(5, 4096) {
+                let x = to_q31(x[0] * 0.25);
+                fixed.process(x, theta_q31);
+                float.process(Simd::splat(from_q31(x)), Simd::splat(from_q31(theta_q31)));
+
+                let lp_error = (from_q31(fixed.get_lowpass()) - float.get_lowpass()[0]).abs();
+                let hp_error = (from_q31(fixed.get_highpass()) - float.get_highpass()[0]).abs();
+                assert!(
+                    lp_error.max(hp_error) < 1e-6,
+                    "theta = {theta}: errors {lp_error}, {hp_error}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reset_clears_outputs() {
+        let mut filter = Q31OnePole::default();
+        filter.process(to_q31(0.5), Q31OnePole::theta_to_q31(0.1));
+        filter.reset();
+
+        assert_eq!(filter, Q31OnePole::default());
+        assert_eq!(filter.get_passthrough(), 0);
+        assert_eq!(filter.get_lowpass(), 0);
+        assert_eq!(filter.get_highpass(), 0);
+    }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+/// The subset of `std::simd::StdFloat` used by this crate, which is only available with
+/// `std`, with the same names and signatures, computed lane by lane, through `libm`
+pub trait StdFloat: Sized {
+    /// Fused multiply-add, `self * a + b`, with a single rounding
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn log2(self) -> Self;
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+}
+
+#[inline]
+fn map<T: SimdElement, const N: usize>(x: Simd<T, N>, f: impl FnMut(T) -> T) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_array(x.to_array().map(f))
+}
+
+impl<const N: usize> StdFloat for Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Simd::from_array(core::array::from_fn(|i| libm::fmaf(self[i], a[i], b[i])))
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        map(self, libm::sqrtf)
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        map(self, libm::expf)
+    }
+
+    #[inline]
+    fn exp2(self) -> Self {
+        map(self, libm::exp2f)
+    }
+
+    #[inline]
+    fn log2(self) -> Self {
+        map(self, libm::log2f)
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        map(self, libm::floorf)
+    }
+
+    #[inline]
+    fn round(self) -> Self {
+        map(self, libm::roundf)
+    }
+}
+
+impl<const N: usize> StdFloat for Simd<f64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Simd::from_array(core::array::from_fn(|i| libm::fma(self[i], a[i], b[i])))
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        map(self, libm::sqrt)
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        map(self, libm::exp)
+    }
+
+    #[inline]
+    fn exp2(self) -> Self {
+        map(self, libm::exp2)
+    }
+
+    #[inline]
+    fn log2(self) -> Self {
+        map(self, libm::log2)
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        map(self, libm::floor)
+    }
+
+    #[inline]
+    fn round(self) -> Self {
+        map(self, libm::round)
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+/// Polyphase halfband lowpass FIR filter, for the up and downsampling stages of a 2x
+/// oversampler, cutting off at a quarter of the (high) sample rate.
+///
+/// The kernel is a Blackman-Harris windowed sinc of `2 * TAPS - 1` taps, centered on a tap
+/// of `0.5`. Every other tap, starting from the center, is exactly `0`, so only the `TAPS`
+/// remaining ones need to be stored and multiplied: the polyphase decomposition splits the
+/// filter into one branch holding them, running at the low sample rate, and one consisting
+/// of a pure delay. `TAPS` must be even and non-zero. The transition band narrows as `TAPS`
+/// grows: with `16`, frequencies above `0.37` times the high sample rate are attenuated by
+/// more than `90 dB`, and the passband droops by `0.6 dB` at `0.2`, with `32`, these become
+/// `0.31` and `0.003 dB`.
+///
+/// A given instance must be used for only one direction, either [`Self::upsample`] or
+/// [`Self::downsample`]. Either way, it is linear phase, with a latency of `TAPS - 1`
+/// samples, at the high sample rate.
+#[derive(Clone, Copy)]
+pub struct HalfbandFir<const TAPS: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    coeffs: [f32; TAPS],
+    /// delay line of the branch holding the non-zero taps, newest sample first
+    even: [VFloat<N>; TAPS],
+    /// delay line of the odd-indexed input of `downsample`, newest sample first
+    odd: [VFloat<N>; TAPS],
+}
+
+impl<const TAPS: usize, const N: usize> Default for HalfbandFir<TAPS, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const TAPS: usize, const N: usize> HalfbandFir<TAPS, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    pub fn new() -> Self {
+        assert!(
+            TAPS != 0 && TAPS.is_multiple_of(2),
+            "the number of taps must be even and non-zero"
+        );
+
+        let len = 2 * TAPS - 1;
+        let center = TAPS - 1;
+        let mut coeffs = [0.; TAPS];
+
+        // the non-zero taps are the even-indexed ones of the full kernel
+        let mut sum = 0.;
+        for (k, coeff) in coeffs.iter_mut().enumerate() {
+            let n = 2 * k;
+            let offset = n as f64 - center as f64;
+            let sinc =
+                (core::f64::consts::FRAC_PI_2 * offset).sin() / (core::f64::consts::PI * offset);
+
+            let t = core::f64::consts::TAU * (n as f64 + 1.) / (len as f64 + 1.);
+            let window =
+                0.35875 - 0.48829 * t.cos() + 0.14128 * (2. * t).cos() - 0.01168 * (3. * t).cos();
+
+            let h = sinc * window;
+            sum += h;
+            *coeff = h as f32;
+        }
+
+        // normalize to a DC gain of exactly 1, the center tap contributing 0.5
+        let norm = (0.5 / sum) as f32;
+        coeffs.iter_mut().for_each(|c| *c *= norm);
+
+        Self {
+            coeffs,
+            even: [Simd::splat(0.); TAPS],
+            odd: [Simd::splat(0.); TAPS],
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.even = [Simd::splat(0.); TAPS];
+        self.odd = [Simd::splat(0.); TAPS];
+    }
+
+    /// The non-zero taps of the kernel, the center one (`0.5`) excepted,
+    /// i.e. those of the low rate FIR branch
+    #[inline]
+    pub fn coeffs(&self) -> &[f32; TAPS] {
+        &self.coeffs
+    }
+
+    #[inline]
+    fn push(line: &mut [VFloat<N>; TAPS], x: VFloat<N>) {
+        line.copy_within(..TAPS - 1, 1);
+        line[0] = x;
+    }
+
+    #[inline]
+    fn convolve(&self) -> VFloat<N> {
+        self.coeffs
+            .iter()
+            .zip(&self.even)
+            .fold(Simd::splat(0.), |acc, (&c, &x)| {
+                x.mul_add(Simd::splat(c), acc)
+            })
+    }
+
+    /// Feed one sample at the low sample rate, and return the two corresponding samples,
+    /// in chronological order, at the high sample rate, interpolated (i.e. zero-stuffed,
+    /// with a gain of `2`, and filtered), preserving the signal's level
+    #[inline]
+    pub fn upsample(&mut self, x: VFloat<N>) -> [VFloat<N>; 2] {
+        Self::push(&mut self.even, x);
+        [self.convolve() * Simd::splat(2.), self.even[TAPS / 2 - 1]]
+    }
+
+    /// Feed two samples, in chronological order, at the high sample rate, and return
+    /// the corresponding sample at the low sample rate, filtered, then decimated
+    #[inline]
+    pub fn downsample(&mut self, [x0, x1]: [VFloat<N>; 2]) -> VFloat<N> {
+        Self::push(&mut self.odd, x0);
+        Self::push(&mut self.even, x1);
+        self.odd[TAPS / 2 - 1].mul_add(Simd::splat(0.5), self.convolve())
+    }
+}
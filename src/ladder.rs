@@ -0,0 +1,191 @@
+use super::*;
+
+/// Digital implementation of the Moog-style transistor ladder filter: four identical one-pole
+/// lowpass stages in series, with the output of the last one fed back, inverted, to the
+/// input. Based on the one in the book The Art of VA Filter Design by Vadim Zavalishin.
+///
+/// The feedback loop is solved instantaneously (zero-delay feedback), as in the other TPT
+/// filters of this crate, so the cutoff and resonance stay accurate at all frequencies, and
+/// the filter is stable for any feedback amount in `[0 ; 4[`, self-oscillating as it
+/// approaches `4`. This is the linear model, without the saturation of the transistors.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Ladder<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    stages: [Integrator<N>; 4],
+    x: VFloat<N>,
+    u: VFloat<N>,
+}
+
+impl<const N: usize> Ladder<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Set the states, and every output, to `0.0`
+    #[inline]
+    pub fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+            stage.flush_output();
+        }
+        self.x = Simd::splat(0.);
+        self.u = Simd::splat(0.);
+    }
+
+    /// Update the filter's internal state.
+    ///
+    /// This should be called _only once_ per sample, _every sample_
+    ///
+    /// `g` is the `tan(w_c/2)` of every stage, as in [`SVF::process`](crate::svf::SVF::process),
+    /// and must be positive.
+    ///
+    /// `k` is the amount of feedback, in `[0 ; 4]`: `0` gives four plain one-pole stages,
+    /// and the filter self-oscillates (at the cutoff frequency) at `4`. As in the analog
+    /// circuit, the passband gain of the `4`-pole output drops to `1 / (1 + k)` as the
+    /// resonance rises.
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, g: VFloat<N>, k: VFloat<N>) {
+        self.x = x;
+        let one = Simd::splat(1.);
+        let theta = g / (one + g);
+        let one_m_theta = one - theta;
+
+        // each stage outputs theta * input + (1 - theta) * state, so the last one outputs
+        // theta^4 * u + s, where s only depends on the states...
+        let s = self.stages.iter().fold(Simd::splat(0.), |acc, stage| {
+            acc.mul_add(theta, one_m_theta * stage.state())
+        });
+
+        // ... and the input of the first one is u = x - k * (theta^4 * u + s)
+        let theta2 = theta * theta;
+        let u = k.mul_add(-s, x) / k.mul_add(theta2 * theta2, one);
+        self.u = u;
+
+        self.stages.iter_mut().fold(u, |y, stage| {
+            stage.process((y - stage.state()) * theta);
+            *stage.output()
+        });
+    }
+
+    #[inline]
+    pub fn get_passthrough(&self) -> &VFloat<N> {
+        &self.x
+    }
+
+    /// Output of the first stage, a `6 dB/oct` lowpass inside the feedback loop
+    #[inline]
+    pub fn get_lowpass1(&self) -> &VFloat<N> {
+        self.stages[0].output()
+    }
+
+    /// Output of the second stage, a `12 dB/oct` lowpass inside the feedback loop
+    #[inline]
+    pub fn get_lowpass2(&self) -> &VFloat<N> {
+        self.stages[1].output()
+    }
+
+    /// Output of the last stage, the classic `24 dB/oct` resonant lowpass, whose transfer
+    /// function is `1 / ((1 + s)^4 + k)`, see [`transfer::low_pass4`]
+    #[inline]
+    pub fn get_lowpass4(&self) -> &VFloat<N> {
+        self.stages[3].output()
+    }
+
+    /// Resonant `24 dB/oct` highpass
+    ///
+    /// A one-pole highpass being its input minus its lowpass, the input of the first
+    /// stage (after the feedback), `u`, passed through four of them, is the binomial mix
+    /// `u - 4 * y1 + 6 * y2 - 4 * y3 + y4` of the stages' outputs `y1..y4`. The transfer
+    /// function is `s^4 / ((1 + s)^4 + k)` (see [`transfer::high_pass4`]), sharing the poles
+    /// of the lowpass, so `k` emphasizes the corner just the same, up to self-oscillation at
+    /// `4`, but, unlike the lowpass', the passband gain stays at `1` for all values of `k`.
+    #[inline]
+    pub fn get_hp24(&self) -> VFloat<N> {
+        let [y1, y2, y3, y4] = self.stages.each_ref().map(|stage| *stage.output());
+        (y1 + y3).mul_add(Simd::splat(-4.), y2.mul_add(Simd::splat(6.), self.u + y4))
+    }
+}
+
+#[cfg(feature = "num")]
+pub mod transfer {
+
+    use super::*;
+
+    #[inline]
+    fn h_denominator<T: Float>(s: Complex<T>, k: T) -> Complex<T> {
+        (s + T::one()).powi(4) + k
+    }
+
+    /// [`Ladder::get_lowpass4`], `k` being the feedback amount passed to [`Ladder::process`]
+    #[inline]
+    pub fn low_pass4<T: Float>(s: Complex<T>, k: T) -> Complex<T> {
+        h_denominator(s, k).finv()
+    }
+
+    /// [`Ladder::get_hp24`], `k` being the feedback amount passed to [`Ladder::process`]
+    #[inline]
+    pub fn high_pass4<T: Float>(s: Complex<T>, k: T) -> Complex<T> {
+        s.powi(4).fdiv(h_denominator(s, k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_clears_every_output() {
+        let mut ladder = Ladder::<4>::default();
+        let g = Simd::splat(0.3);
+        let k = Simd::splat(2.);
+
+        for x in test_noise(5, 64) {
+            ladder.process(x, g, k);
+        }
+
+        ladder.reset();
+        assert!(ladder == Ladder::default());
+        assert_eq!(*ladder.get_passthrough(), Simd::splat(0.));
+        assert_eq!(ladder.get_hp24(), Simd::splat(0.));
+    }
+
+    #[cfg(feature = "num")]
+    #[test]
+    fn outputs_match_transfer_functions() {
+        let g = Simd::from_array([0.02, 0.2, 1., 4.]);
+        let k = Simd::from_array([0., 1., 3., 3.8]);
+
+        let mut ladder = Ladder::default();
+        let (lp, hp): (Vec<_>, Vec<_>) = (0..16384)
+            .map(|i| {
+                ladder.process(Simd::splat(if i == 0 { 1. } else { 0. }), g, k);
+                (*ladder.get_lowpass4(), ladder.get_hp24())
+            })
+            .unzip();
+
+        for ratio in [0.25, 0.5, 0.9, 1., 1.1, 2., 4.] {
+            // the frequency at which the prototype is evaluated at s = j * ratio
+            let w = Simd::from_array(g.to_array().map(|g| 2. * (ratio * g).atan()));
+            let lp_gain = dft_magnitude(&lp, w);
+            let hp_gain = dft_magnitude(&hp, w);
+
+            for lane in 0..4 {
+                let s = Complex::new(0., f64::from(ratio));
+                let k = f64::from(k[lane]);
+                for (gain, expected) in [
+                    (lp_gain[lane], transfer::low_pass4(s, k).norm()),
+                    (hp_gain[lane], transfer::high_pass4(s, k).norm()),
+                ] {
+                    // the highpass' binomial mix loses some absolute precision in the stopband
+                    let error = (f64::from(gain) - expected).abs();
+                    assert!(
+                        error < 1e-4 * expected + 1e-5,
+                        "at {ratio} * w_c, g: {}, k: {k}, got {gain}, expected {expected}",
+                        g[lane],
+                    );
+                }
+            }
+        }
+    }
+}
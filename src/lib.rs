@@ -1,16 +1,130 @@
 #![feature(portable_simd)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("the `libm` feature is required without `std`, for the floating point math");
 
 use simd_util::{
     math,
-    simd::{LaneCount, Simd, StdFloat, SupportedLaneCount},
+    simd::{cmp::SimdPartialOrd, num::SimdFloat, Mask, Simd, SimdElement},
+};
+
+#[cfg(not(feature = "std"))]
+use float::StdFloat;
+#[cfg(feature = "std")]
+use simd_util::simd::StdFloat;
+
+// Const generic parameters can't carry trait bounds, and the where clauses of a trait
+// aren't implied where it is used, so there is no `where N: ValidLanes` shorthand for
+// `LaneCount<N>: SupportedLaneCount`: code generic over the lane count has to spell it
+// out. These are re-exported so that it doesn't need to depend on `simd_util` for it.
+pub use simd_util::{
+    simd::{LaneCount, SupportedLaneCount},
     VFloat, FLOATS_PER_VECTOR,
 };
 
 #[cfg(feature = "num")]
 use num::{Complex, Float, One};
 
+/// Lane element type of the vectors processed by [`Integrator`], [`svf::SVF`] and
+/// [`one_pole::OnePole`]: `f32` (the default, that of [`VFloat`]), or `f64`, e.g. for offline
+/// processing, where the accuracy matters more than the throughput.
+pub trait FloatElement: SimdElement + Default + PartialEq + PartialOrd + core::fmt::Debug {
+    /// `x`, converted (exactly, `f32` being the narrowest element type) to this type
+    fn from_f32(x: f32) -> Self;
+}
+
+impl FloatElement for f32 {
+    #[inline]
+    fn from_f32(x: f32) -> Self {
+        x
+    }
+}
+
+impl FloatElement for f64 {
+    #[inline]
+    fn from_f32(x: f32) -> Self {
+        x.into()
+    }
+}
+
+/// The arithmetic, comparisons and lane-wise functions the filters need from `Simd<T, N>`,
+/// implemented for every vector of [`FloatElement`]s.
+///
+/// As with `LaneCount<N>: SupportedLaneCount`, code generic over the element type has to
+/// spell it out, as `Simd<T, N>: FloatVector<T, N>`.
+pub trait FloatVector<T: SimdElement, const N: usize>:
+    Copy
+    + Default
+    + PartialEq
+    + core::ops::Add<Output = Self>
+    + core::ops::Sub<Output = Self>
+    + core::ops::Mul<Output = Self>
+    + core::ops::Div<Output = Self>
+    + core::ops::Neg<Output = Self>
+    + StdFloat
+    + SimdFloat<Mask = Mask<T::Mask, N>>
+    + SimdPartialOrd<Mask = Mask<T::Mask, N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+}
+
+impl<T: SimdElement, const N: usize, V> FloatVector<T, N> for V
+where
+    LaneCount<N>: SupportedLaneCount,
+    V: Copy
+        + Default
+        + PartialEq
+        + core::ops::Add<Output = Self>
+        + core::ops::Sub<Output = Self>
+        + core::ops::Mul<Output = Self>
+        + core::ops::Div<Output = Self>
+        + core::ops::Neg<Output = Self>
+        + StdFloat
+        + SimdFloat<Mask = Mask<T::Mask, N>>
+        + SimdPartialOrd<Mask = Mask<T::Mask, N>>,
+{
+}
+
+/// `x`, converted to `T`, in every lane
+#[inline]
+pub(crate) fn splat<T: FloatElement, const N: usize>(x: f32) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::splat(T::from_f32(x))
+}
+
+pub mod biquad;
+#[cfg(feature = "num")]
+pub mod bode;
+pub mod crossover;
+pub mod decimation;
+pub mod effects;
+pub mod envelope;
+pub mod eq;
+pub mod fixed_point;
+#[cfg(not(feature = "std"))]
+mod float;
+pub mod halfband;
+pub mod ladder;
+pub mod modulation;
+pub mod nonlin;
 pub mod one_pole;
+pub mod smooth;
 pub mod svf;
+pub mod wrappers;
+
+/// With the `flush_denormals` feature enabled, [`Integrator`] states
+/// whose magnitude falls below this are flushed to `0.0`.
+///
+/// `1e-20` (`-400 dB` relative to full scale) is far below anything audible, or even
+/// representable by a 32-bit integer DAC, so flushing never colors the signal, yet it is
+/// some `18` orders of magnitude above the subnormal range (below `1.2e-38`), so, once a
+/// filter falls silent, its states reach it, and are zeroed, long before they could
+/// become subnormal, and slow down the arithmetic on some CPUs.
+pub const DENORMAL_THRESHOLD: f32 = 1e-20;
 
 /// Transposed Direct Form II Trapezoidal Integrator, but without the `0.5` pre-gain.
 ///
@@ -26,30 +140,140 @@ pub mod svf;
 /// Transfer function:
 ///
 /// `(z + 1) / (z - 1)`
-#[derive(Default, Clone, Copy)]
-pub struct Integrator<const N: usize = FLOATS_PER_VECTOR>
+///
+/// The lanes are `f32` by default, see [`FloatElement`] for `f64` ones.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Integrator<const N: usize = FLOATS_PER_VECTOR, T: FloatElement = f32>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    s: VFloat<N>,
-    out: VFloat<N>,
+    s: Simd<T, N>,
+    out: Simd<T, N>,
 }
 
-impl<const N: usize> Integrator<N>
+impl<const N: usize, T: FloatElement> Integrator<N, T>
 where
     LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
 {
     /// Feed the provided input `sample` (`x[n]`),
     /// update the system's internal state (`v[n]`),
     /// and return the system's next output (`y[n]`)
     #[inline]
-    pub fn process(&mut self, x: VFloat<N>) {
+    pub fn process(&mut self, x: Simd<T, N>) {
         self.out = x + self.s;
         self.s = self.out + x;
+
+        #[cfg(feature = "flush_denormals")]
+        {
+            let tiny = self.s.abs().simd_lt(splat(DENORMAL_THRESHOLD));
+            self.s = tiny.select(splat(0.), self.s);
+        }
     }
 
     #[inline]
     /// Get thecurrent `y[n]` state
+    pub fn output(&self) -> &Simd<T, N> {
+        &self.out
+    }
+
+    /// Set the internal `v[n]` state to `0.0`
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s = splat(0.);
+    }
+
+    /// Get the current `v[n]` state
+    #[inline]
+    pub fn state(&self) -> &Simd<T, N> {
+        &self.s
+    }
+
+    /// Set the internal `v[n]` state to `s`
+    #[inline]
+    pub fn set_state(&mut self, s: Simd<T, N>) {
+        self.s = s;
+    }
+
+    /// Set the cached `y[n]` output to `0.0`, leaving the `v[n]` state untouched
+    #[inline]
+    pub fn flush_output(&mut self) {
+        self.out = splat(0.);
+    }
+}
+
+impl<const N: usize> Integrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Lane-wise select between the states (and outputs) of `a` and `b`,
+    /// picking `a`'s in the lanes where `mask` is set
+    #[inline]
+    pub(crate) fn select(mask: Mask<i32, N>, a: &Self, b: &Self) -> Self {
+        Self {
+            s: mask.select(a.s, b.s),
+            out: mask.select(a.out, b.out),
+        }
+    }
+
+    /// Returns `true` if, in every lane, this integrator's `v[n]` state
+    /// is within `epsilon` of `other`'s
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.s - other.s).abs().simd_le(Simd::splat(epsilon)).all()
+    }
+}
+
+/// Prints the state and output as plain per-lane arrays
+impl<const N: usize, T: FloatElement> core::fmt::Debug for Integrator<N, T>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Integrator")
+            .field("state", &self.s.to_array())
+            .field("output", &self.out.to_array())
+            .finish()
+    }
+}
+
+/// Variant of [`Integrator`] whose internal state is passed through a nonlinearity
+/// (typically a soft-clipper) every sample, modeling the saturation of an analog integrator.
+///
+/// Its difference equations are:
+///
+/// `y[n] = x[n] + v[n-1]`
+///
+/// `v[n] = f(y[n] + x[n])`
+///
+/// where `f` is the nonlinearity passed to `process`. If `f` is bounded, so is the state,
+/// regardless of the input. With `f(x) = x`, this is exactly an [`Integrator`]. For the
+/// `tanh`-like saturation of analog circuits, [`nonlin::tanh`] (possibly scaled, to set the
+/// clipping level) is a cheap choice requiring no additional dependencies.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct SaturatingIntegrator<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    s: VFloat<N>,
+    out: VFloat<N>,
+}
+
+impl<const N: usize> SaturatingIntegrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Feed the provided input `sample` (`x[n]`),
+    /// update the system's internal state (`v[n]`), saturating it with `f`,
+    /// and return the system's next output (`y[n]`)
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, f: impl FnOnce(VFloat<N>) -> VFloat<N>) {
+        self.out = x + self.s;
+        self.s = f(self.out + x);
+    }
+
+    /// Get the current `y[n]` state
+    #[inline]
     pub fn output(&self) -> &VFloat<N> {
         &self.out
     }
@@ -65,4 +289,663 @@ where
     pub fn state(&self) -> &VFloat<N> {
         &self.s
     }
+
+    /// Set the internal `v[n]` state to `s`
+    #[inline]
+    pub fn set_state(&mut self, s: VFloat<N>) {
+        self.s = s;
+    }
+}
+
+/// Error returned by the validating coefficient constructors, such as
+/// [`svf::SvfCoeffs::try_new`] and [`one_pole::try_theta`], naming the parameter that is
+/// out of its stable range, and the lanes where it is, as a bitmask (bit `i` for lane `i`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoeffError {
+    /// `g` is negative, infinite or NaN
+    G { lanes: u64 },
+    /// `res` is negative, infinite or NaN
+    Res { lanes: u64 },
+    /// `theta` is outside of `[0 ; 1]`, or NaN
+    Theta { lanes: u64 },
+}
+
+impl core::fmt::Display for CoeffError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (param, range, lanes) = match self {
+            Self::G { lanes } => ("g", "finite and >= 0", lanes),
+            Self::Res { lanes } => ("res", "finite and >= 0", lanes),
+            Self::Theta { lanes } => ("theta", "in [0, 1]", lanes),
+        };
+        write!(
+            f,
+            "`{param}` must be {range}, which isn't the case in lanes "
+        )?;
+        let mut lanes = (0..u64::BITS).filter(|i| lanes & (1 << i) != 0);
+        if let Some(first) = lanes.next() {
+            write!(f, "{first}")?;
+        }
+        lanes.try_for_each(|i| write!(f, ", {i}"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoeffError {}
+
+/// Pack per-voice values (e.g. cutoffs, resonances or input samples) into a vector, one voice
+/// per lane, `voices[i]` going into lane `i`, the lanes past the last voice being set to
+/// `fill`, e.g. a neutral coefficient for unused voices.
+///
+/// Every filter in this crate processes its lanes independently, so, with one voice per lane,
+/// each voice behaves exactly as if it were run alone, in its own filter, with its own
+/// coefficients.
+///
+/// # Panics
+///
+/// If there are more than `N` voices
+#[inline]
+pub fn from_voices<const N: usize>(voices: &[f32], fill: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    assert!(voices.len() <= N, "at most one voice per lane");
+    let mut lanes = [fill; N];
+    lanes[..voices.len()].copy_from_slice(voices);
+    Simd::from_array(lanes)
+}
+
+/// Given `g = tan(w_c/2)`, return `tan(w_c * ratio / 2)`, clamped, like the
+/// `*_from_hz` helpers, to just below nyquist
+#[inline]
+fn scale_prewarped<const N: usize>(g: VFloat<N>, ratio: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let max = 0.999 * core::f32::consts::FRAC_PI_2;
+    Simd::from_array(
+        g.to_array()
+            .map(|g| (g.atan() * ratio).clamp(0., max).tan()),
+    )
+}
+
+/// Minimize `cost` over `K` parameters, each given as a `(min, max, grid_steps)` range
+/// (`grid_steps >= 2`), returning the best parameters found, and their cost.
+///
+/// The parameters are first searched for on a coarse grid, of `grid_steps` evenly spaced
+/// values (bounds included) along each axis, from the best point of which a pattern search
+/// follows: each parameter in turn is moved by its step size, in either direction, keeping
+/// any improvement, and the step sizes, starting with the grid's spacing, are halved whenever
+/// no neighbouring point improves the cost, until they fall below `1e-4`. Parameters are
+/// clamped to their ranges throughout. This is a local search, so it only finds the global
+/// minimum if the grid is fine enough to start in its basin.
+#[cfg(any(feature = "std", feature = "num"))]
+fn grid_pattern_search<const K: usize>(
+    ranges: [(f64, f64, usize); K],
+    mut cost: impl FnMut([f64; K]) -> f64,
+) -> ([f64; K], f64) {
+    let grid_point = |mut index: usize| {
+        let mut p = [0.; K];
+        // the last axis varies fastest
+        for (p, &(min, max, steps)) in p.iter_mut().zip(&ranges).rev() {
+            *p = min + (max - min) * (index % steps) as f64 / (steps - 1) as f64;
+            index /= steps;
+        }
+        p
+    };
+
+    let grid_len = ranges.iter().map(|&(_, _, steps)| steps).product();
+    let mut best = [0.; K];
+    let mut best_cost = f64::INFINITY;
+
+    for p in (0..grid_len).map(grid_point) {
+        let c = cost(p);
+        if c < best_cost {
+            (best, best_cost) = (p, c);
+        }
+    }
+
+    // start with the grid's spacing
+    let mut steps = ranges.map(|(min, max, steps)| (max - min) / (steps - 1) as f64);
+
+    while steps.iter().any(|&step| step > 1e-4) {
+        let mut improved = false;
+
+        for axis in 0..K {
+            for dir in [-1., 1.] {
+                let mut p = best;
+                let (min, max, _) = ranges[axis];
+                p[axis] = (p[axis] + dir * steps[axis]).clamp(min, max);
+                let c = cost(p);
+                if c < best_cost {
+                    (best, best_cost) = (p, c);
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            for step in &mut steps {
+                *step *= 0.5;
+            }
+        }
+    }
+
+    (best, best_cost)
+}
+
+/// Generate a vector whose lanes are independently, uniformly distributed in `range`
+#[cfg(feature = "arbitrary")]
+#[inline]
+fn arbitrary_vfloat<const N: usize>(
+    u: &mut arbitrary::Unstructured,
+    range: core::ops::RangeInclusive<f32>,
+) -> arbitrary::Result<VFloat<N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (&start, &end) = (range.start(), range.end());
+    let mut lanes = [0.; N];
+    for lane in &mut lanes {
+        let t = u.int_in_range(0..=u32::MAX)? as f32 / u32::MAX as f32;
+        *lane = t.mul_add(end - start, start).clamp(start, end);
+    }
+    Ok(Simd::from_array(lanes))
+}
+
+/// Bound on the magnitude of the integrator states generated by the `Arbitrary` impls
+#[cfg(feature = "arbitrary")]
+const ARBITRARY_STATE_MAX: f32 = 16.;
+
+/// Generates states in `[-16 ; 16]`, with the output consistent with them,
+/// as if `process(0.0)` had just been called
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for Integrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let s = arbitrary_vfloat(u, -ARBITRARY_STATE_MAX..=ARBITRARY_STATE_MAX)?;
+        Ok(Self { s, out: s })
+    }
+}
+
+/// Fixed-size array, (de)serialized as a tuple of `K` elements, for any `K`
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy)]
+struct SerdeArray<T, const K: usize>([T; K]);
+
+#[cfg(feature = "serde")]
+impl<T: Copy + Default, const K: usize> Default for SerdeArray<T, K> {
+    #[inline]
+    fn default() -> Self {
+        Self([T::default(); K])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const K: usize> serde::Serialize for SerdeArray<T, K> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(K)?;
+        for elem in &self.0 {
+            tuple.serialize_element(elem)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Copy + Default, const K: usize> serde::Deserialize<'de>
+    for SerdeArray<T, K>
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayVisitor<T, const K: usize>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de> + Copy + Default, const K: usize>
+            serde::de::Visitor<'de> for ArrayVisitor<T, K>
+        {
+            type Value = SerdeArray<T, K>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "a tuple of {K} elements")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut array = SerdeArray::default();
+                for (i, elem) in array.0.iter_mut().enumerate() {
+                    *elem = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                Ok(array)
+            }
+        }
+
+        deserializer.deserialize_tuple(K, ArrayVisitor(core::marker::PhantomData))
+    }
+}
+
+/// Serialize the state vectors `vs` of a filter as a tuple of `K`
+/// tuples of `N` floats, one per vector, holding its lanes
+#[cfg(feature = "serde")]
+#[inline]
+fn serialize_vfloats<S: serde::Serializer, const N: usize, const K: usize>(
+    vs: [VFloat<N>; K],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    serde::Serialize::serialize(
+        &SerdeArray(vs.map(|v| SerdeArray(v.to_array()))),
+        serializer,
+    )
+}
+
+/// Inverse of [`serialize_vfloats`]
+#[cfg(feature = "serde")]
+#[inline]
+fn deserialize_vfloats<'de, D: serde::Deserializer<'de>, const N: usize, const K: usize>(
+    deserializer: D,
+) -> Result<[VFloat<N>; K], D::Error>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let SerdeArray(vs) =
+        <SerdeArray<SerdeArray<f32, N>, K> as serde::Deserialize>::deserialize(deserializer)?;
+    Ok(vs.map(|SerdeArray(lanes)| Simd::from_array(lanes)))
+}
+
+/// Serialized as a tuple of the state and the cached output,
+/// each a tuple of the `N` lanes
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Integrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_vfloats([self.s, self.out], serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Integrator<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [s, out] = deserialize_vfloats(deserializer)?;
+        Ok(Self { s, out })
+    }
+}
+
+/// Common interface to the filters provided by this crate, allowing code
+/// to be written generically over (or dynamically dispatch between) them.
+pub trait Filter<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// The coefficients driving this filter's `process` method
+    type Coeffs;
+    /// Selects one of the outputs this filter is capable of producing
+    type Shape;
+
+    /// Update the filter's internal state. This should be called
+    /// _only once_ per sample, _every sample_
+    fn process(&mut self, x: VFloat<N>, coeffs: &Self::Coeffs);
+
+    /// Get the output corresponding to `shape`, computed by the last `process` call
+    fn get(&self, shape: Self::Shape) -> VFloat<N>;
+
+    /// Set the internal state to `0.0`
+    fn reset(&mut self);
+
+    /// Estimate of this filter's latency, in samples, given its coefficients.
+    ///
+    /// IIR filters don't have a constant delay. This is the group delay, at DC, induced by the
+    /// filter's poles, which is the delay of the lowpass output for low frequencies.
+    fn latency(&self, coeffs: &Self::Coeffs) -> VFloat<N>;
+
+    /// Run `samples` iterations of `process`, with the held input `x`, discarding the
+    /// outputs, to let the filter settle (e.g. after a drastic coefficient change),
+    /// so that its initial transient doesn't leak into the audio.
+    ///
+    /// How many samples are needed depends on the filter's decay time: for example, an
+    /// [`SVF`](svf::SVF)'s transients decay by a factor of roughly `e` every `1 / (g * res)`
+    /// samples for `res < 2`.
+    #[inline]
+    fn prime(&mut self, x: VFloat<N>, coeffs: &Self::Coeffs, samples: usize) {
+        for _ in 0..samples {
+            self.process(x, coeffs);
+        }
+    }
+
+    /// Same as `process`, but then adds the output selected by `shape` to `out`, e.g. to
+    /// sum a bank of parallel filters into a common output, without an intermediate buffer
+    /// per filter.
+    #[inline]
+    fn process_add(
+        &mut self,
+        x: VFloat<N>,
+        coeffs: &Self::Coeffs,
+        shape: Self::Shape,
+        out: &mut VFloat<N>,
+    ) {
+        self.process(x, coeffs);
+        *out += self.get(shape);
+    }
+
+    /// Filter `input` into `output`, with the constant coefficients `coeffs`, writing the
+    /// output selected by `shape`. The state carries across calls, so splitting a signal
+    /// into blocks gives exactly the same results as processing it sample by sample.
+    ///
+    /// # Panics
+    ///
+    /// If `input` and `output` don't have the same length
+    #[inline]
+    fn process_block(
+        &mut self,
+        input: &[VFloat<N>],
+        output: &mut [VFloat<N>],
+        coeffs: &Self::Coeffs,
+        shape: Self::Shape,
+    ) where
+        Self::Shape: Copy,
+    {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "input and output lengths must match"
+        );
+
+        for (&x, y) in input.iter().zip(output) {
+            self.process(x, coeffs);
+            *y = self.get(shape);
+        }
+    }
+
+    /// Same as [`Self::process_block`], but with one set of coefficients per sample,
+    /// `coeffs[i]` being used for `input[i]`, e.g. for audio-rate modulation
+    ///
+    /// # Panics
+    ///
+    /// If `input`, `output` and `coeffs` don't all have the same length
+    #[inline]
+    fn process_block_modulated(
+        &mut self,
+        input: &[VFloat<N>],
+        output: &mut [VFloat<N>],
+        coeffs: &[Self::Coeffs],
+        shape: Self::Shape,
+    ) where
+        Self::Shape: Copy,
+    {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "input and output lengths must match"
+        );
+        assert_eq!(
+            input.len(),
+            coeffs.len(),
+            "input and coeffs lengths must match"
+        );
+
+        for ((&x, coeffs), y) in input.iter().zip(coeffs).zip(output) {
+            self.process(x, coeffs);
+            *y = self.get(shape);
+        }
+    }
+
+    /// Freeze the filter's current settings into an FIR kernel of `len` taps: the first `len`
+    /// samples of the impulse response of the output selected by `shape`, in the first lane.
+    ///
+    /// The filter itself is left untouched, a reset copy of it is fed the impulse instead.
+    ///
+    /// No window is applied, the response is simply truncated (i.e. rectangular window). The
+    /// tail of an IIR filter decays exponentially, but slowly for low cutoffs or high
+    /// resonance, in which case `len` must be large enough, or the truncation will show up as
+    /// ripple in the kernel's frequency response (and a DC gain error for lowpass shapes).
+    #[cfg(feature = "std")]
+    fn to_fir(&self, coeffs: &Self::Coeffs, shape: Self::Shape, len: usize) -> Vec<f32>
+    where
+        Self: Clone,
+        Self::Shape: Copy,
+    {
+        self.impulse_response(coeffs, shape, len)
+            .into_iter()
+            .map(|y| y[0])
+            .collect()
+    }
+
+    /// The first `len` samples of the impulse response of the output selected by `shape`,
+    /// in every lane. As with [`to_fir`](Filter::to_fir), the filter itself is left
+    /// untouched, a reset copy of it is fed the (unit) impulse instead.
+    #[cfg(feature = "std")]
+    fn impulse_response(
+        &self,
+        coeffs: &Self::Coeffs,
+        shape: Self::Shape,
+        len: usize,
+    ) -> Vec<VFloat<N>>
+    where
+        Self: Clone,
+        Self::Shape: Copy,
+    {
+        let mut filter = self.clone();
+        filter.reset();
+
+        (0..len)
+            .map(|i| {
+                filter.process(Simd::splat(if i == 0 { 1. } else { 0. }), coeffs);
+                filter.get(shape)
+            })
+            .collect()
+    }
+
+    /// The first `len` samples of the response of the output selected by `shape` to a unit
+    /// step (starting at the first sample), in every lane, computed from a reset copy of the
+    /// filter, as in [`impulse_response`](Filter::impulse_response). It settles to the
+    /// output's DC gain.
+    #[cfg(feature = "std")]
+    fn step_response(&self, coeffs: &Self::Coeffs, shape: Self::Shape, len: usize) -> Vec<VFloat<N>>
+    where
+        Self: Clone,
+        Self::Shape: Copy,
+    {
+        let mut filter = self.clone();
+        filter.reset();
+
+        (0..len)
+            .map(|_| {
+                filter.process(Simd::splat(1.), coeffs);
+                filter.get(shape)
+            })
+            .collect()
+    }
+}
+
+/// `len` samples of white noise, uniform in `[-1 ; 1[`, and independent in each lane,
+/// the same `seed` always giving the same noise, for tests
+#[cfg(test)]
+fn test_noise<const N: usize>(seed: u32, len: usize) -> Vec<VFloat<N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut rng = modulation::seed_lanes(seed);
+    (0..len).map(|_| modulation::uniform(&mut rng)).collect()
+}
+
+/// Magnitude, in every lane, of the DTFT of `ir` (e.g. an impulse
+/// response), at the angular frequency `omega`, in radians per sample, for tests
+#[cfg(test)]
+fn dft_magnitude<const N: usize>(ir: &[VFloat<N>], omega: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_array(core::array::from_fn(|lane| {
+        let omega = f64::from(omega[lane]);
+        let (re, im) = ir.iter().enumerate().fold((0., 0.), |(re, im), (n, y)| {
+            let (sin, cos) = (omega * n as f64).sin_cos();
+            let y = f64::from(y[lane]);
+            (y.mul_add(cos, re), y.mul_add(-sin, im))
+        });
+        re.hypot(im) as f32
+    }))
+}
+
+/// DTFT, in every lane, of `ir` (e.g. an impulse response), at the
+/// angular frequency `omega`, in radians per sample, for tests
+#[cfg(all(test, feature = "std", feature = "num"))]
+fn dtft<const N: usize>(ir: &[VFloat<N>], omega: f64) -> [Complex<f64>; N]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    core::array::from_fn(|lane| {
+        ir.iter()
+            .enumerate()
+            .map(|(n, y)| Complex::from_polar(f64::from(y[lane]), -omega * n as f64))
+            .sum()
+    })
+}
+
+/// Serialize `value` to JSON, and deserialize it back, for tests
+#[cfg(all(test, feature = "serde"))]
+fn serde_round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+/// `len` bytes of deterministic pseudo-random data, the same `seed` always giving the same
+/// bytes, to feed the `Arbitrary` impls in tests
+#[cfg(all(test, feature = "arbitrary"))]
+fn test_bytes(seed: u32, len: usize) -> Vec<u8> {
+    // xorshift32, which must be seeded with a non-zero state
+    let mut state = seed.max(1);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saturating_integrator_state_stays_bounded() {
+        let mut integrator = SaturatingIntegrator::<4>::default();
+        let amplitude = Simd::from_array([1., 10., 1e3, 1e6]);
+
+        for i in 0..10_000 {
+            // mostly positive, to wind up a linear integrator
+            let x = if i % 7 == 0 { -amplitude } else { amplitude };
+            integrator.process(x, nonlin::tanh);
+
+            let &s = integrator.state();
+            assert!(s.abs().simd_le(Simd::splat(1.)).all(), "state: {s:?}");
+            // y = x + s, up to the rounding of the sum
+            let &y = integrator.output();
+            let tolerance = amplitude * Simd::splat(f32::EPSILON);
+            assert!(
+                (y - x).abs().simd_le(Simd::splat(1.) + tolerance).all(),
+                "output: {y:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn voices_behave_like_independent_filters() {
+        use svf::SVF;
+
+        let (g, res) = ([0.01, 0.3, 2.], [0.2, 1., 1.8]);
+        // the unused lane gets neutral coefficients
+        let (g_lanes, res_lanes) = (from_voices::<4>(&g, 0.), from_voices::<4>(&res, 1.));
+        assert_eq!(g_lanes.to_array(), [0.01, 0.3, 2., 0.]);
+        assert_eq!(res_lanes.to_array(), [0.2, 1., 1.8, 1.]);
+
+        let input = test_noise::<4>(1, 2048);
+        let mut voices = SVF::<4>::default();
+        let mut filters = [SVF::<1>::default(); 3];
+
+        for x in input {
+            let x = from_voices::<4>(&x.to_array()[..3], 0.);
+            voices.process(x, g_lanes, res_lanes);
+
+            for (voice, filter) in filters.iter_mut().enumerate() {
+                let lane = |y: VFloat<4>| Simd::splat(y[voice]);
+                filter.process(lane(x), Simd::splat(g[voice]), Simd::splat(res[voice]));
+
+                let (expected, y) = (filter.outputs(), voices.outputs());
+                for (y, expected) in [
+                    (y.lowpass, expected.lowpass),
+                    (y.bandpass, expected.bandpass),
+                    (y.highpass, expected.highpass),
+                ] {
+                    assert_eq!(lane(y).to_bits(), expected.to_bits(), "voice {voice}");
+                }
+            }
+            assert_eq!(voices.get_lowpass()[3], 0., "unused lane");
+        }
+    }
+
+    #[test]
+    fn prime_settles_to_the_held_input() {
+        use svf::{SvfCoeffs, SVF};
+
+        let coeffs = SvfCoeffs::new(Simd::from_array([0.01, 0.1, 1., 5.]), Simd::splat(0.7));
+        let x = Simd::from_array([0.5, -0.25, 1., 0.]);
+
+        let mut primed = SVF::<4>::default();
+        primed.prime(x, &coeffs, 5000);
+
+        let &lp = primed.get_lowpass();
+        assert!(
+            (lp - x).abs().simd_lt(Simd::splat(1e-5)).all(),
+            "lowpass: {lp:?}"
+        );
+        let &hp = primed.get_highpass();
+        assert!(
+            hp.abs().simd_lt(Simd::splat(1e-5)).all(),
+            "highpass: {hp:?}"
+        );
+
+        // no more than running the filter for as many samples
+        let mut processed = SVF::<4>::default();
+        for _ in 0..5000 {
+            Filter::process(&mut processed, x, &coeffs);
+        }
+        assert!(primed == processed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn integrator_resumes_identically_after_serde_round_trip() {
+        let input = test_noise::<4>(6, 1024);
+        let mut integrator = Integrator::<4>::default();
+        for &x in &input[..512] {
+            integrator.process(x * Simd::splat(0.01));
+        }
+
+        let mut restored = serde_round_trip(&integrator);
+        assert_eq!(restored.output().to_bits(), integrator.output().to_bits());
+
+        for &x in &input[512..] {
+            integrator.process(x * Simd::splat(0.01));
+            restored.process(x * Simd::splat(0.01));
+            assert_eq!(restored.output().to_bits(), integrator.output().to_bits());
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use super::*;
+
+use core::f32::consts::TAU;
+use one_pole::{theta, OnePole};
+use simd_util::simd::{cmp::SimdOrd, num::SimdUint};
+
+/// Advance the xorshift32 generators `rng`, which must be non-zero,
+/// and return their new states, mapped to uniform noise in `[-1 ; 1[`
+#[inline]
+pub(crate) fn uniform<const N: usize>(rng: &mut Simd<u32, N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut r = *rng;
+    r ^= r << Simd::splat(13);
+    r ^= r >> Simd::splat(17);
+    r ^= r << Simd::splat(5);
+    *rng = r;
+
+    // 24 random bits, mapped to [-1 ; 1[
+    (r >> Simd::splat(8)).cast::<f32>() * Simd::splat(1. / (1 << 23) as f32) - Simd::splat(1.)
+}
+
+/// Derive the states of `N` independent xorshift32 generators, one per lane, from `seed`,
+/// the same seed always giving the same states
+#[inline]
+pub(crate) fn seed_lanes<const N: usize>(seed: u32) -> Simd<u32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_array(core::array::from_fn(|lane| {
+        // xorshift generators are stuck at zero, and must be seeded with a non-zero state
+        seed.wrapping_add(0x9e37_79b9_u32.wrapping_mul(lane as u32 + 1))
+            .max(1)
+    }))
+}
+
+/// Slowly varying random offset, e.g. to add to a filter's coefficients
+/// to emulate the cutoff instability of analog circuits.
+///
+/// Uniform white noise, generated independently in each lane, is smoothed by a one-pole
+/// lowpass filter, whose cutoff sets the rate of the drift. The output is scaled such that
+/// its standard deviation is `amount`, regardless of the rate.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Drift<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    rng: Simd<u32, N>,
+    lp: OnePole<N>,
+    theta: VFloat<N>,
+    scale: VFloat<N>,
+    amount: VFloat<N>,
+}
+
+impl<const N: usize> Drift<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a silent (`amount = 0`) drift source, with every lane's random
+    /// number generator derived from `seed`. The same seed produces the same output.
+    #[inline]
+    pub fn new(seed: u32) -> Self {
+        let mut this = Self {
+            rng: Simd::splat(0),
+            lp: OnePole::default(),
+            theta: Simd::splat(0.),
+            scale: Simd::splat(0.),
+            amount: Simd::splat(0.),
+        };
+        this.set_seed(seed);
+        this
+    }
+
+    /// Restart the random sequences from `seed`, and reset the smoothing filter
+    #[inline]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.rng = seed_lanes(seed);
+        self.lp.reset();
+    }
+
+    /// Set the rate of the drift, i.e. the cutoff frequency, in Hz, of the smoothing filter,
+    /// which must be positive, and below `sample_rate / 2`
+    #[inline]
+    pub fn set_rate(&mut self, rate_hz: VFloat<N>, sample_rate: f32) {
+        self.theta = theta(rate_hz * Simd::splat(TAU / sample_rate));
+        // the lowpass' noise power gain is exactly theta, and that of
+        // the uniform noise in [-1 ; 1[, 1/3, we normalize both away
+        self.scale = (self.theta * Simd::splat(1. / 3.)).sqrt().recip();
+    }
+
+    /// Set the standard deviation of the output
+    #[inline]
+    pub fn set_amount(&mut self, amount: VFloat<N>) {
+        self.amount = amount;
+    }
+
+    /// Advance the drift by one sample, and return the new offset
+    #[inline]
+    pub fn tick(&mut self) -> VFloat<N> {
+        let noise = uniform(&mut self.rng);
+        self.lp.process(noise, self.theta);
+        self.amount * self.scale * self.lp.get_lowpass()
+    }
+}
+
+/// Fixed, per-lane random deviations of a filter's coefficients, emulating the component
+/// tolerances that make every voice of a polyphonic analog synthesizer sound slightly
+/// different, each lane being a voice.
+///
+/// Unlike [`Drift`], the deviations don't change over time: they are drawn once, from the
+/// per-lane seeds, and the same seeds always give the same voices.
+#[derive(Clone, Copy, PartialEq)]
+pub struct VoiceSpread<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    g_scale: VFloat<N>,
+    res_scale: VFloat<N>,
+}
+
+impl<const N: usize> Default for VoiceSpread<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// No spread, every voice is identical
+    #[inline]
+    fn default() -> Self {
+        Self {
+            g_scale: Simd::splat(1.),
+            res_scale: Simd::splat(1.),
+        }
+    }
+}
+
+impl<const N: usize> VoiceSpread<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Draw the deviations of each voice from its seed in `seeds`, uniformly, and
+    /// independently for `g` and `res`, within `spread_percent` percent (clamped to
+    /// `[0 ; 100]`) of their base values.
+    #[inline]
+    pub fn new(spread_percent: f32, seeds: Simd<u32, N>) -> Self {
+        // scramble the seeds, the first outputs of neighbouring states being correlated.
+        // xorshift generators are stuck at zero, and must be seeded with a non-zero state
+        let mut rng =
+            (seeds * Simd::splat(0x9e37_79b9) + Simd::splat(0x6a09_e667)).simd_max(Simd::splat(1));
+        for _ in 0..4 {
+            uniform(&mut rng);
+        }
+
+        let spread = Simd::splat(spread_percent.clamp(0., 100.) * 0.01);
+        let one = Simd::splat(1.);
+
+        Self {
+            g_scale: uniform(&mut rng).mul_add(spread, one),
+            res_scale: uniform(&mut rng).mul_add(spread, one),
+        }
+    }
+
+    /// Apply each voice's deviations to `base_g` and `base_res`, returning the `g` and `res`
+    /// to pass to [`SVF::process`](crate::svf::SVF::process), or to any filter taking an
+    /// integrator pre-gain and a damping factor.
+    ///
+    /// `g` is scaled directly, rather than the cutoff frequency, which is the same for
+    /// cutoffs well below nyquist, and slightly smaller deviations in cutoff above. Both
+    /// results stay positive, so they remain stable if the base values are.
+    #[inline]
+    pub fn apply(&self, base_g: VFloat<N>, base_res: VFloat<N>) -> (VFloat<N>, VFloat<N>) {
+        (base_g * self.g_scale, base_res * self.res_scale)
+    }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+/// Cheap, smooth soft-clipper: `y = d * x / (1 + |d * x|)`, where `d` is `drive`.
+///
+/// The transfer curve is odd-symmetric and monotonic, has a slope of `drive` at the origin,
+/// reaches `+/-0.5` at `x = +/-1 / drive`, and approaches (but never reaches) `+/-1`
+/// asymptotically. Its slope is continuous everywhere, so it doesn't generate the harsh
+/// high harmonics of hard clipping, although it starts compressing earlier than `tanh`
+/// (and approaches its asymptotes more slowly: `1 - y ~ 1 / (d * x)`).
+///
+/// The output is always bounded, making this suitable for feedback paths. It is
+/// branchless, and only costs an `abs`, an addition, a multiplication, and a division
+/// per lane. `drive` must be positive. NaN inputs produce NaN outputs.
+#[inline]
+pub fn saturate<const N: usize>(x: VFloat<N>, drive: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let dx = drive * x;
+    dx / (Simd::splat(1.) + dx.abs())
+}
+
+/// Cheap rational approximation of `tanh`: `x * (27 + x^2) / (27 + 9 * x^2)`, for `x` clamped
+/// to `[-3 ; 3]`.
+///
+/// At `+/-3`, the curve reaches exactly `+/-1`, with a slope of `0`, so, with the clamp, it is
+/// bounded, and its slope continuous everywhere. It has a unit slope at the origin, and stays
+/// within `0.025` of `tanh`. Unlike [`saturate`], it leaves small signals almost untouched
+/// (the error is cubic), and clips firmly, making it suitable for modeling the saturation
+/// of analog integrators, see [`SaturatingIntegrator`]. NaN inputs produce NaN outputs.
+#[inline]
+pub fn tanh<const N: usize>(x: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let x = x.simd_clamp(Simd::splat(-3.), Simd::splat(3.));
+    let x2 = x * x;
+    x * (Simd::splat(27.) + x2) / x2.mul_add(Simd::splat(9.), Simd::splat(27.))
+}
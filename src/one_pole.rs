@@ -12,29 +12,163 @@ where
     g / (Simd::splat(1.) + g)
 }
 
+/// Compute the "filtering factor" `theta` (see [`OnePole::process`]) from a cutoff
+/// frequency `freq`, in Hz.
+///
+/// Like [`svf::g_from_hz`], the cutoff is clamped to `[0 ; 0.999 * nyquist]`, so sweeping it
+/// past nyquist (e.g. with an envelope) holds the filter at `0.999 * nyquist`, where `theta`
+/// is just below `1`, instead of wrapping around, or producing a NaN. Negative (and NaN)
+/// frequencies are treated as `0`, where the lowpass holds its state.
+#[inline]
+pub fn theta_from_hz<const N: usize>(freq: VFloat<N>, sample_rate: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let w_c = freq * Simd::splat(core::f32::consts::TAU / sample_rate);
+    theta(w_c.simd_clamp(Simd::splat(0.), Simd::splat(0.999 * core::f32::consts::PI)))
+}
+
+/// Compute `theta` such that the `-3 dB` point of the lowpass (and highpass) output lands
+/// at `freq_hz`, in each lane.
+///
+/// With the bilinear transform, no correction is needed: the prototype `1 / (1 + s)` is at
+/// `-3 dB` at `s = j`, and the digital frequency `w` is mapped to the analog one `tan(w/2) /
+/// g`, so prewarping the cutoff with [`theta`] already places the `-3 dB` point exactly at
+/// `w_c`, at all frequencies, including close to nyquist (cramping shapes the rest of the
+/// response, not that point). The residual error is that of the single precision evaluation
+/// of `tan`, a relative error in frequency on the order of `1e-7`. This is just
+/// [`theta_from_hz`], clamp included. See [`theta_matched_z`] for a mapping where this
+/// doesn't hold.
+#[inline]
+pub fn theta_from_minus3db<const N: usize>(freq_hz: VFloat<N>, sample_rate: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    theta_from_hz(freq_hz, sample_rate)
+}
+
+/// Alternative to [`theta`] based on the matched-Z transform, instead of the bilinear one.
+///
+/// `w_c` must be positive, in radians per sample, and returns `(1 - e^(-w_c)) / 2`.
+///
+/// The filter's pole, `1 - 2 * theta`, is then exactly `e^(-w_c)`, the analog pole `-w_c`
+/// mapped with `z = e^s`. Consequently, the lowpass' impulse and step responses decay with
+/// exactly the analog time constant, `1 / w_c` samples, making this the better choice when
+/// designing from time constants (envelopes, smoothing, slew). The price is that, unlike
+/// with `theta`, the magnitude response no longer hits `-3 dB` exactly at `w_c`: it is
+/// accurate at low frequencies, and deviates as `w_c` approaches nyquist. Furthermore, since
+/// the pole is always positive, `theta` stays below `0.5`, meaning cutoffs above
+/// `sample_rate / 4` (in the bilinear sense) can't be reached.
+///
+/// Both mappings keep the zero at nyquist of the trapezoidal integrator.
+#[inline]
+pub fn theta_matched_z<const N: usize>(w_c: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    (Simd::splat(1.) - (-w_c).exp()) * Simd::splat(0.5)
+}
+
+/// Returns `theta` if, in every lane, it lies in the range `[0 ; 1]`, in which
+/// [`OnePole::process`] is stable, and an error naming the offending lanes otherwise
+#[inline]
+pub fn try_theta<const N: usize, T: FloatElement>(
+    theta: Simd<T, N>,
+) -> Result<Simd<T, N>, CoeffError>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
+{
+    // NaNs fail both comparisons
+    let bad = !(theta.simd_ge(splat(0.)) & theta.simd_le(splat(1.)));
+    if bad.any() {
+        Err(CoeffError::Theta {
+            lanes: bad.to_bitmask(),
+        })
+    } else {
+        Ok(theta)
+    }
+}
+
+/// Group delay, in samples, of the one-pole filter at DC, given its "filtering factor" `theta`
+///
+/// If `g = tan(w_c/2)`, this is `1 / (2 * g) = (1 - theta) / (2 * theta)`. This is also
+/// the group delay at DC of the highpass output (the allpass' is twice as long).
+#[inline]
+pub fn latency<const N: usize>(theta: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    (Simd::splat(1.) - theta) / (theta + theta)
+}
+
 /// Digital implementation of the analogue one-pole filter. Based on the
 /// one in the book The Art of VA Filter Design by Vadim Zavalishin.
 ///
 /// Capable of outputing many different shapes,
 /// (highpass, lowpass, allpass, shelving....)
-#[derive(Default)]
-pub struct OnePole<const N: usize = FLOATS_PER_VECTOR>
+///
+/// The lanes are `f32` by default. With `f64` ones (see [`FloatElement`]), only the core
+/// methods (processing, resetting and the outputs) are available, the coefficient helpers
+/// of this module, and the other processing variants, being specific to `f32`.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct OnePole<const N: usize = FLOATS_PER_VECTOR, T: FloatElement = f32>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    lp: Integrator<N>,
-    x: VFloat<N>,
+    lp: Integrator<N, T>,
+    x: Simd<T, N>,
 }
 
-impl<const N: usize> OnePole<N>
+/// Prints the integrator state, followed by the cached outputs, as plain per-lane arrays
+impl<const N: usize, T: FloatElement> core::fmt::Debug for OnePole<N, T>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OnePole")
+            .field("state", &self.lp.state().to_array())
+            .field("x", &self.x.to_array())
+            .field("lp", &self.get_lowpass().to_array())
+            .finish()
+    }
+}
+
+impl<const N: usize, T: FloatElement> OnePole<N, T>
 where
     LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
 {
     #[inline]
     pub fn reset(&mut self) {
         self.lp.reset()
     }
 
+    /// Set the internal state such that the lowpass output settles at `x` immediately,
+    /// i.e. as if the filter had been fed the constant input `x` for a very long time.
+    #[inline]
+    pub fn reset_to(&mut self, x: Simd<T, N>) {
+        self.lp.set_state(x);
+        // with theta = 0, the state is left untouched, only the outputs are updated
+        self.process(x, splat(0.));
+    }
+
+    /// Set the cached outputs to `0.0`, leaving the integrator state untouched,
+    /// see [`SVF::flush_outputs`](crate::svf::SVF::flush_outputs)
+    #[inline]
+    pub fn flush_outputs(&mut self) {
+        self.x = splat(0.);
+        self.lp.flush_output();
+    }
+
+    /// Square of the integrator state, in each lane, see [`SVF::state_energy`](crate::svf::SVF::state_energy)
+    #[inline]
+    pub fn state_energy(&self) -> Simd<T, N> {
+        let &s = self.lp.state();
+        s * s
+    }
+
     /// Update the filter's internal state.
     ///
     /// This should be called _only once_ per sample, _every sample_
@@ -52,41 +186,473 @@ where
     /// Notice that `theta = 0 -> w_c = 0`, the output will be silent.
     ///
     /// Furthermore, `theta = 1 -> w_c = pi (nyquist)`, the output will be the same as the input.
+    ///
+    /// In debug builds, this panics if `theta` is out of range (see [`try_theta`]),
+    /// in any lane. See [`Self::process_clamped`] to clamp it instead.
     #[inline]
-    pub fn process(&mut self, x: VFloat<N>, theta: VFloat<N>) {
+    pub fn process(&mut self, x: Simd<T, N>, theta: Simd<T, N>) {
+        debug_assert!(
+            try_theta(theta).is_ok(),
+            "unstable one-pole filtering factor, theta: {:?}",
+            theta.to_array(),
+        );
+
         self.x = x;
-        self.lp.process((x - self.lp.state()) * theta);
+        self.lp.process((x - *self.lp.state()) * theta);
     }
 
+    /// Same as [`Self::process`], returning the lowpass output, for single-output use
     #[inline]
-    pub fn get_passthrough(&self) -> &VFloat<N> {
+    pub fn process_lowpass(&mut self, x: Simd<T, N>, theta: Simd<T, N>) -> Simd<T, N> {
+        self.process(x, theta);
+        *self.get_lowpass()
+    }
+
+    /// Same as [`Self::process`], returning the highpass output, for single-output use
+    #[inline]
+    pub fn process_highpass(&mut self, x: Simd<T, N>, theta: Simd<T, N>) -> Simd<T, N> {
+        self.process(x, theta);
+        self.get_highpass()
+    }
+
+    #[inline]
+    pub fn get_passthrough(&self) -> &Simd<T, N> {
         &self.x
     }
 
     #[inline]
-    pub fn get_lowpass(&self) -> &VFloat<N> {
+    pub fn get_lowpass(&self) -> &Simd<T, N> {
         self.lp.output()
     }
 
     #[inline]
-    pub fn get_highpass(&self) -> VFloat<N> {
+    pub fn get_highpass(&self) -> Simd<T, N> {
         self.get_passthrough() - self.get_lowpass()
     }
 
     #[inline]
-    pub fn get_allpass(&self) -> VFloat<N> {
+    pub fn get_allpass(&self) -> Simd<T, N> {
         self.get_lowpass() - self.get_highpass()
     }
 
     #[inline]
-    pub fn get_low_shelf(&self, gain: VFloat<N>) -> VFloat<N> {
+    pub fn get_low_shelf(&self, gain: Simd<T, N>) -> Simd<T, N> {
         gain.mul_add(*self.get_lowpass(), self.get_highpass())
     }
 
     #[inline]
-    pub fn get_high_shelf(&self, gain: VFloat<N>) -> VFloat<N> {
+    pub fn get_high_shelf(&self, gain: Simd<T, N>) -> Simd<T, N> {
         gain.mul_add(self.get_highpass(), *self.get_lowpass())
     }
+
+    /// Tilt EQ: the lows are scaled by `1 / sqrt(gain)`, and the highs by `sqrt(gain)`,
+    /// `gain` being the (linear) ratio of the latter to the former, i.e. the high shelf
+    /// output, normalized by `sqrt(gain)`.
+    ///
+    /// As with the shelf outputs, the transition is centered below the cutoff for `gain > 1`
+    /// (and above, for `gain < 1`), at `cutoff / sqrt(gain)`, roughly. To center it on `w_c`
+    /// exactly, as in [`transfer::tilting`], which this then matches, pass
+    /// `theta = g / (1 + g)`, with `g = tan(w_c/2) * sqrt(gain)`.
+    #[inline]
+    pub fn get_tilt(&self, gain: Simd<T, N>) -> Simd<T, N> {
+        let m = gain.sqrt();
+        m.mul_add(self.get_highpass(), self.get_lowpass() / m)
+    }
+}
+
+impl<const N: usize> OnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Compute the "filtering factor" `theta` that keeps the same analog cutoff frequency (in Hz)
+    /// as `theta` after a sample rate change from `old_sample_rate` to `new_sample_rate`.
+    ///
+    /// If `rescale_state` is `true`, the integrator state is also re-seeded so that the lowpass
+    /// output stays continuous across the change. As with [`SVF::retune`](crate::svf::SVF::retune),
+    /// this is not exact, but greatly reduces the transient.
+    #[inline]
+    pub fn retune(
+        &mut self,
+        old_sample_rate: f32,
+        new_sample_rate: f32,
+        theta: VFloat<N>,
+        rescale_state: bool,
+    ) -> VFloat<N> {
+        let one = Simd::splat(1.);
+        let g = scale_prewarped(theta / (one - theta), old_sample_rate / new_sample_rate);
+
+        if rescale_state {
+            let &lp = self.get_lowpass();
+            self.lp.set_state(g.mul_add(self.get_highpass(), lp));
+        }
+
+        g / (one + g)
+    }
+
+    /// Returns `true` if, in every lane, this filter's integrator state
+    /// is within `epsilon` of `other`'s
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.lp.approx_eq(&other.lp, epsilon)
+    }
+
+    /// Same as [`Self::process`], but first clamps `theta` into `[0 ; 1]`, the range in which
+    /// the filter is stable, NaNs being replaced with `0.0`, e.g. for live modulation, where
+    /// surviving a stray out of range value beats blowing up.
+    #[inline]
+    pub fn process_clamped(&mut self, x: VFloat<N>, theta: VFloat<N>) {
+        let zero = Simd::splat(0.);
+        let clamped = theta.simd_clamp(zero, Simd::splat(1.));
+        self.process(x, theta.is_nan().select(zero, clamped));
+    }
+
+    /// Same as [`Self::process`], with the same `theta` in every lane
+    #[inline]
+    pub fn process_scalar(&mut self, x: VFloat<N>, theta: f32) {
+        self.process(x, Simd::splat(theta));
+    }
+}
+
+/// Variant of [`OnePole`] whose integrator is leaky: its state is multiplied by a `leak`
+/// factor every sample, so that it decays towards zero even with a constant input, e.g.
+/// for control signal smoothing that should fall back to zero.
+///
+/// With no input, the state decays by a factor of `leak * (1 - 2 * theta)` every sample.
+/// With `leak = 1`, this is exactly a [`OnePole`].
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct LeakyOnePole<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    lp: Integrator<N>,
+    x: VFloat<N>,
+}
+
+impl<const N: usize> LeakyOnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.lp.reset()
+    }
+
+    /// Update the filter's internal state, see [`OnePole::process`].
+    ///
+    /// `leak` is the factor, in `]0 ; 1]`, by which the state is multiplied before
+    /// each update. Lower values mean faster decay and a lower DC gain, see [`Self::dc_gain`].
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, theta: VFloat<N>, leak: VFloat<N>) {
+        self.x = x;
+        let s = self.lp.state() * leak;
+        self.lp.set_state(s);
+        self.lp.process((x - s) * theta);
+    }
+
+    /// The gain of the lowpass output at DC (i.e. its steady-state response
+    /// to a constant input), `theta * (1 + leak) / (1 - leak + 2 * theta * leak)`.
+    ///
+    /// This is `1` when `leak = 1`, and drops below unity as soon as `leak < 1`.
+    /// Conversely, the DC gain of the highpass output becomes non-zero.
+    #[inline]
+    pub fn dc_gain(theta: VFloat<N>, leak: VFloat<N>) -> VFloat<N> {
+        let one = Simd::splat(1.);
+        let two_theta = theta + theta;
+        theta * (one + leak) / two_theta.mul_add(leak, one - leak)
+    }
+
+    #[inline]
+    pub fn get_passthrough(&self) -> &VFloat<N> {
+        &self.x
+    }
+
+    #[inline]
+    pub fn get_lowpass(&self) -> &VFloat<N> {
+        self.lp.output()
+    }
+
+    #[inline]
+    pub fn get_highpass(&self) -> VFloat<N> {
+        self.get_passthrough() - self.get_lowpass()
+    }
+}
+
+/// One-pole lowpass filter whose "filtering factor" differs depending on whether the input
+/// is above (rising) or below (falling) the current output, chosen independently in each lane,
+/// e.g. for slew limiting with different up and down rates.
+///
+/// Unlike [`EnvelopeFollower`](crate::envelope::EnvelopeFollower), the input isn't rectified,
+/// so this works on bipolar and DC-coupled signals.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct AsymmetricOnePole<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    lp: OnePole<N>,
+}
+
+impl<const N: usize> AsymmetricOnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.lp.reset();
+    }
+
+    /// Feed the input sample `x`, and return the updated output.
+    ///
+    /// `rise_theta` and `fall_theta` are the filtering factors (see [`OnePole::process`])
+    /// used when `x` is, respectively, above or below the current output.
+    #[inline]
+    pub fn process(
+        &mut self,
+        x: VFloat<N>,
+        rise_theta: VFloat<N>,
+        fall_theta: VFloat<N>,
+    ) -> VFloat<N> {
+        let rising = x.simd_gt(*self.lp.get_lowpass());
+        self.lp.process(x, rising.select(rise_theta, fall_theta));
+        *self.lp.get_lowpass()
+    }
+
+    #[inline]
+    pub fn get_output(&self) -> &VFloat<N> {
+        self.lp.get_lowpass()
+    }
+}
+
+/// Default corner frequency, in Hz, of a [`DcBlocker`]: low enough to leave
+/// the audible bass untouched, high enough to settle quickly
+pub const DC_BLOCKER_CUTOFF_HZ: f32 = 10.;
+
+/// One-pole highpass filter with a very low cutoff, removing the DC offset of a signal.
+///
+/// At low cutoffs, the per-sample updates of a [`OnePole`]'s state are tiny compared to the
+/// state itself, which tracks the DC offset, and those smaller than half its rounding step
+/// are lost, leaving the state stuck up to `ulp(offset) / (4 * theta)` away from the
+/// offset, i.e. a residual DC in the highpass output. For an offset of `1000` and a `10 Hz`
+/// corner at `48 kHz`, that's around `0.05`. To avoid this, the integrator state is
+/// accumulated with Kahan (compensated) summation, carrying the rounding error of every
+/// update into the next, and the highpass output is computed directly from the (small)
+/// difference between the input and the state, rather than as `x - lp`. The residual DC is
+/// then on the order of the input's own rounding, over arbitrarily long runs.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DcBlocker<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    s: VFloat<N>,
+    // rounding error of the last update of `s` (the exact state is `s - comp`)
+    comp: VFloat<N>,
+    theta: VFloat<N>,
+    hp: VFloat<N>,
+}
+
+impl<const N: usize> DcBlocker<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a DC blocker with its corner at [`DC_BLOCKER_CUTOFF_HZ`]
+    #[inline]
+    pub fn new(sample_rate: f32) -> Self {
+        Self::with_cutoff(Simd::splat(DC_BLOCKER_CUTOFF_HZ), sample_rate)
+    }
+
+    /// Create a DC blocker with its corner at `freq_hz`, see [`Self::set_cutoff`]
+    #[inline]
+    pub fn with_cutoff(freq_hz: VFloat<N>, sample_rate: f32) -> Self {
+        Self {
+            s: Simd::splat(0.),
+            comp: Simd::splat(0.),
+            theta: theta_from_hz(freq_hz, sample_rate),
+            hp: Simd::splat(0.),
+        }
+    }
+
+    /// Set the corner (`-3 dB`) frequency, in Hz, clamped as in [`theta_from_hz`]
+    #[inline]
+    pub fn set_cutoff(&mut self, freq_hz: VFloat<N>, sample_rate: f32) {
+        self.theta = theta_from_hz(freq_hz, sample_rate);
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.s = Simd::splat(0.);
+        self.comp = Simd::splat(0.);
+        self.hp = Simd::splat(0.);
+    }
+
+    /// Feed the input sample `x`, and return `x`, with its DC offset removed
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>) -> VFloat<N> {
+        let d = x - self.s + self.comp;
+        let v = d * self.theta;
+        self.hp = d - v;
+
+        // s += 2 * v, compensated
+        let y = v + v - self.comp;
+        let t = self.s + y;
+        self.comp = (t - self.s) - y;
+        self.s = t;
+
+        self.hp
+    }
+
+    #[inline]
+    pub fn get_output(&self) -> &VFloat<N> {
+        &self.hp
+    }
+}
+
+/// Generates a bounded integrator state, see [`Integrator`]'s impl,
+/// with the cached outputs cleared, as with [`OnePole::flush_outputs`]
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for OnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut one_pole = Self {
+            lp: arbitrary::Arbitrary::arbitrary(u)?,
+            x: Simd::splat(0.),
+        };
+        one_pole.flush_outputs();
+        Ok(one_pole)
+    }
+}
+
+/// Serialized as a tuple of the input, and the integrator's state and output, each a tuple of
+/// the `N` lanes, see [`SVF`](crate::svf::SVF)'s impl
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for OnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_vfloats([self.x, self.lp.s, self.lp.out], serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for OnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, s, out] = deserialize_vfloats(deserializer)?;
+        Ok(Self {
+            lp: Integrator { s, out },
+            x,
+        })
+    }
+}
+
+/// Generate "filtering factors" `theta` (see [`OnePole::process`]) uniformly distributed
+/// in the stable range `[0 ; 1]`, for fuzzing
+#[cfg(feature = "arbitrary")]
+#[inline]
+pub fn arbitrary_theta<const N: usize>(
+    u: &mut arbitrary::Unstructured,
+) -> arbitrary::Result<VFloat<N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    arbitrary_vfloat(u, 0.0..=1.)
+}
+
+/// The different outputs of the [`OnePole`] filter.
+///
+/// The gain carried by the shelving variants follows the same
+/// convention as the corresponding `OnePole::get_*_shelf` method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterShape<G> {
+    Passthrough,
+    Lowpass,
+    Highpass,
+    Allpass,
+    LowShelf(G),
+    HighShelf(G),
+}
+
+impl<G> FilterShape<G> {
+    /// Convert the gain carried by the shelving variants with `f`
+    #[inline]
+    pub fn map<H>(self, f: impl FnOnce(G) -> H) -> FilterShape<H> {
+        match self {
+            Self::Passthrough => FilterShape::Passthrough,
+            Self::Lowpass => FilterShape::Lowpass,
+            Self::Highpass => FilterShape::Highpass,
+            Self::Allpass => FilterShape::Allpass,
+            Self::LowShelf(gain) => FilterShape::LowShelf(f(gain)),
+            Self::HighShelf(gain) => FilterShape::HighShelf(f(gain)),
+        }
+    }
+
+    /// Theoretical asymptotic slope, in dB per octave, of this shape's rolloff, away from its
+    /// passband: `-6 dB/oct` (precisely `-6.02`) for the lowpass and highpass, and `0.0` for
+    /// the others. See [`svf::FilterShape::slope_db_per_octave`](crate::svf::FilterShape::slope_db_per_octave)
+    #[inline]
+    pub fn slope_db_per_octave(&self) -> f64 {
+        match self {
+            Self::Lowpass | Self::Highpass => -svf::DB_PER_OCTAVE_PER_ORDER,
+            Self::Passthrough | Self::Allpass | Self::LowShelf(_) | Self::HighShelf(_) => 0.,
+        }
+    }
+}
+
+/// Scalar convenience methods, see [`SVF`](crate::svf::SVF)'s
+impl OnePole<1> {
+    /// Same as [`Self::process`], with a single lane
+    #[inline]
+    pub fn process_mono(&mut self, x: f32, theta: f32) {
+        self.process(Simd::splat(x), Simd::splat(theta));
+    }
+
+    /// Get the output selected by `shape`, see [`Filter::get`]
+    #[inline]
+    pub fn get_mono(&self, shape: FilterShape<f32>) -> f32 {
+        self.get(shape.map(Simd::splat))[0]
+    }
+}
+
+impl<const N: usize> Filter<N> for OnePole<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// The "filtering factor" `theta`, see [`OnePole::process`]
+    type Coeffs = VFloat<N>;
+    type Shape = FilterShape<VFloat<N>>;
+
+    #[inline]
+    fn process(&mut self, x: VFloat<N>, theta: &Self::Coeffs) {
+        self.process(x, *theta);
+    }
+
+    #[inline]
+    fn get(&self, shape: Self::Shape) -> VFloat<N> {
+        match shape {
+            FilterShape::Passthrough => *self.get_passthrough(),
+            FilterShape::Lowpass => *self.get_lowpass(),
+            FilterShape::Highpass => self.get_highpass(),
+            FilterShape::Allpass => self.get_allpass(),
+            FilterShape::LowShelf(gain) => self.get_low_shelf(gain),
+            FilterShape::HighShelf(gain) => self.get_high_shelf(gain),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    #[inline]
+    fn latency(&self, theta: &Self::Coeffs) -> VFloat<N> {
+        latency(*theta)
+    }
 }
 
 #[cfg(feature = "num")]
@@ -129,4 +695,282 @@ pub mod transfer {
     pub fn high_shelf<T: Float>(s: Complex<T>, gain: T) -> Complex<T> {
         tilting(s, gain).scale(gain.sqrt())
     }
+
+    /// Frequency response of the digital filter itself, at the normalized frequency `omega`
+    /// (in radians per sample, in `[0 ; pi[`), of the output selected by `shape`, given the
+    /// `theta` passed to [`OnePole::process`] (which must be in `]0 ; 1[`), and, for the
+    /// shelving shapes, the gain passed to the corresponding getter.
+    ///
+    /// As with [`svf::trnasfer::z_response`], this is
+    /// evaluated from the filter's structure, so it includes the warping of the bilinear
+    /// transform: the lowpass is `1 / (1 + 1 / i)`, where `i = g * (z + 1) / (z - 1)`, with
+    /// `g = theta / (1 - theta)`, is the integrator's transfer function, which, on the unit
+    /// circle, is the analog prototype evaluated at `s = j * tan(omega / 2) / g`.
+    #[inline]
+    pub fn z_response(shape: FilterShape<f64>, theta: f64, omega: f64) -> Complex<f64> {
+        let one = Complex::<f64>::one();
+        let z = Complex::from_polar(1., omega);
+        let g = theta / (1. - theta);
+
+        // 1 / i, finite except at nyquist
+        let inv_int = (z - one) / ((z + one) * g);
+        let lp = (inv_int + one).finv();
+        let hp = one - lp;
+
+        match shape {
+            FilterShape::Passthrough => one,
+            FilterShape::Lowpass => lp,
+            FilterShape::Highpass => hp,
+            FilterShape::Allpass => lp - hp,
+            FilterShape::LowShelf(gain) => lp * gain + hp,
+            FilterShape::HighShelf(gain) => hp * gain + lp,
+        }
+    }
+
+    /// Group delay, in samples, of the digital filter, at the normalized frequency `omega`,
+    /// i.e. [`group_delay`](crate::bode::group_delay) of [`z_response`], with the same
+    /// parameters.
+    #[inline]
+    pub fn group_delay(shape: FilterShape<f64>, theta: f64, omega: f64) -> f64 {
+        crate::bode::group_delay(|omega| z_response(shape, theta, omega), omega)
+    }
+
+    /// Log-spaced [`sweep`](crate::bode::sweep) of the response of the digital filter, see
+    /// [`z_response`], with the same parameters.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn sweep(
+        shape: FilterShape<f64>,
+        theta: f64,
+        sample_rate: f64,
+        f_lo: f64,
+        f_hi: f64,
+        points: usize,
+    ) -> Vec<(f64, f64, f64)> {
+        crate::bode::sweep(
+            |omega| z_response(shape, theta, omega),
+            sample_rate,
+            f_lo,
+            f_hi,
+            points,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    /// Frequency, in Hz, in every lane, at which the magnitude of the DTFT of `ir` crosses
+    /// `-3 dB`, searched for by bisection, between DC and nyquist. The magnitude must be
+    /// monotonic over that range, and `lowpass` tells whether it decreases.
+    fn minus_3db_hz(ir: &[VFloat<4>], lowpass: bool, sample_rate: f32) -> VFloat<4> {
+        let mut lo = Simd::splat(0.);
+        let mut hi = Simd::splat(sample_rate / 2.);
+        for _ in 0..40 {
+            let mid = (lo + hi) * Simd::splat(0.5);
+            let omega = mid * Simd::splat(core::f32::consts::TAU / sample_rate);
+            let below =
+                dft_magnitude(ir, omega).simd_lt(Simd::splat(core::f32::consts::FRAC_1_SQRT_2));
+            // for a lowpass, being below -3 dB means being past the crossing
+            let past = if lowpass { below } else { !below };
+            hi = past.select(mid, hi);
+            lo = past.select(lo, mid);
+        }
+        (lo + hi) * Simd::splat(0.5)
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn minus_3db_point_lands_on_requested_frequency() {
+        let sample_rate = 48000.;
+        let freq_hz = Simd::from_array([100., 1000., 10000., 22000.]);
+        let theta = theta_from_minus3db(freq_hz, sample_rate);
+
+        for (shape, lowpass) in [(FilterShape::Lowpass, true), (FilterShape::Highpass, false)] {
+            let ir = OnePole::default().impulse_response(&theta, shape, 8192);
+            let measured = minus_3db_hz(&ir, lowpass, sample_rate);
+            assert!(
+                ((measured - freq_hz) / freq_hz)
+                    .abs()
+                    .simd_lt(Simd::splat(1e-5))
+                    .all(),
+                "measured: {measured:?}"
+            );
+        }
+    }
+
+    /// The digital filter is the bilinear transform of the analog prototypes of [`transfer`],
+    /// so its response at `omega` must be theirs at `s = j * tan(omega / 2) / g`, with
+    /// `g = theta / (1 - theta)`
+    #[cfg(all(feature = "std", feature = "num"))]
+    #[test]
+    fn impulse_response_matches_analog_prototypes() {
+        use transfer::*;
+
+        let theta = Simd::from_array([0.01, 0.2, 0.5, 0.9]);
+        let gain = 2.;
+
+        // the analog shelves are centered on their midpoints, at `sqrt(gain)` times (or the
+        // inverse of) the digital filter's cutoff
+        type Prototype = fn(Complex<f64>, f64) -> Complex<f64>;
+        let shapes: [(FilterShape<VFloat<4>>, Prototype); 6] = [
+            (FilterShape::Passthrough, |_, _| Complex::one()),
+            (FilterShape::Lowpass, |s, _| low_pass(s)),
+            (FilterShape::Highpass, |s, _| high_pass(s)),
+            (FilterShape::Allpass, |s, _| all_pass(s)),
+            (FilterShape::LowShelf(Simd::splat(gain)), |s, gain| {
+                low_shelf(s.unscale(gain.sqrt()), gain)
+            }),
+            (FilterShape::HighShelf(Simd::splat(gain)), |s, gain| {
+                high_shelf(s.scale(gain.sqrt()), gain)
+            }),
+        ];
+
+        for (shape, prototype) in shapes {
+            let ir = OnePole::default().impulse_response(&theta, shape, 8192);
+
+            for k in 0..20 {
+                let omega = core::f64::consts::PI * k as f64 / 20.;
+                let measured = dtft(&ir, omega);
+
+                for lane in 0..4 {
+                    let theta = f64::from(theta[lane]);
+                    let s = Complex::new(0., (omega / 2.).tan() * (1. - theta) / theta);
+                    let expected = prototype(s, gain.into());
+                    let error = (measured[lane] - expected).norm();
+                    assert!(
+                        error < 1e-4 * expected.norm() + 1e-5,
+                        "{shape:?}, lane {lane}, omega = {omega}: {} vs {expected}",
+                        measured[lane],
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resumes_identically_after_serde_round_trip() {
+        let theta = Simd::from_array([0.01, 0.2, 0.5, 0.9]);
+        let input = test_noise::<4>(8, 1024);
+        let mut one_pole = OnePole::<4>::default();
+        for &x in &input[..512] {
+            one_pole.process(x, theta);
+        }
+
+        let mut restored = serde_round_trip(&one_pole);
+        let bits = |f: &OnePole<4>| [*f.get_passthrough(), *f.get_lowpass()].map(|y| y.to_bits());
+        assert_eq!(bits(&restored), bits(&one_pole));
+
+        for &x in &input[512..] {
+            one_pole.process(x, theta);
+            restored.process(x, theta);
+            assert_eq!(bits(&restored), bits(&one_pole));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matched_z_impulse_response_decays_like_the_analog_pole() {
+        let w_c = Simd::from_array([0.001, 0.01, 0.1, 1.]);
+        let theta = theta_matched_z(w_c);
+        // short enough for the fastest pole to stay above `DENORMAL_THRESHOLD`
+        let ir = OnePole::default().impulse_response(&theta, FilterShape::Lowpass, 32);
+
+        // the first sample also holds the direct path of the integrator's zero
+        let decay = (-w_c).exp();
+        for h in ir[1..].windows(2) {
+            let ratio = h[1] / h[0];
+            assert!(
+                (ratio - decay).abs().simd_lt(Simd::splat(1e-5)).all(),
+                "ratio: {ratio:?}, expected: {decay:?}"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "num"))]
+    #[test]
+    fn matched_z_impulse_response_matches_z_response() {
+        let w_c = Simd::from_array([0.001, 0.01, 0.1, 1.]);
+        let theta = theta_matched_z(w_c);
+        let ir = OnePole::default().impulse_response(&theta, FilterShape::Lowpass, 1 << 15);
+
+        for k in 0..20 {
+            let omega = core::f64::consts::PI * k as f64 / 20.;
+            let measured = dtft(&ir, omega);
+
+            for lane in 0..4 {
+                let theta = f64::from(theta[lane]);
+                let expected = transfer::z_response(FilterShape::Lowpass, theta, omega);
+                let error = (measured[lane] - expected).norm();
+                assert!(
+                    error < 1e-4 * expected.norm() + 1e-5,
+                    "lane {lane}, omega = {omega}: {} vs {expected}",
+                    measured[lane],
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn f64_lanes_match_f32_ones() {
+        let theta = Simd::from_array([0.01, 0.2, 0.5, 0.9]);
+        let mut single = OnePole::<4>::default();
+        let mut double = OnePole::<4, f64>::default();
+
+        for x in test_noise(7, 2000) {
+            single.process(x, theta);
+            double.process(x.cast(), theta.cast());
+
+            for (a, b) in [
+                (*single.get_lowpass(), *double.get_lowpass()),
+                (single.get_highpass(), double.get_highpass()),
+                (single.get_allpass(), double.get_allpass()),
+            ] {
+                let error = (a.cast::<f64>() - b).abs();
+                assert!(
+                    error.simd_lt(Simd::splat(1e-5)).all(),
+                    "f32: {a:?}, f64: {b:?}"
+                );
+            }
+        }
+    }
+
+    /// Random coefficients, states and inputs, in their stable ranges, must
+    /// never make the outputs blow up, or become NaN or infinite
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn bounded_input_gives_bounded_output() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        const TRIALS: u32 = 64;
+        const SAMPLES: usize = 2048;
+        const BOUND: f32 = 1e3;
+
+        for trial in 1..=TRIALS {
+            let bytes = test_bytes(trial, (SAMPLES + 2) * 8 * 4);
+            let mut u = Unstructured::new(&bytes);
+
+            let mut one_pole = OnePole::<8>::arbitrary(&mut u).unwrap();
+            let theta = arbitrary_theta(&mut u).unwrap();
+
+            for _ in 0..SAMPLES {
+                let x = arbitrary_vfloat(&mut u, -1.0..=1.).unwrap();
+                one_pole.process(x, theta);
+
+                for y in [
+                    *one_pole.get_lowpass(),
+                    one_pole.get_highpass(),
+                    one_pole.get_allpass(),
+                ] {
+                    assert!(
+                        y.is_finite().all() && y.abs().simd_le(Simd::splat(BOUND)).all(),
+                        "output out of bounds: {y:?}, theta: {theta:?}",
+                    );
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,216 @@
+use super::*;
+
+/// Linear ramp between two coefficient values, advanced once per sample.
+///
+/// Intended for cheaply moving a prewarped coefficient (e.g. the SVF's `g` or the one-pole's
+/// `theta`) from one value to another over a block without calling `tan` every sample:
+/// only the two endpoints need to be prewarped, and each [`tick`](Self::tick) costs a single
+/// addition.
+///
+/// # Frequency warping error
+///
+/// Interpolating `g = tan(w_c/2)` linearly is not the same as interpolating `w_c` linearly
+/// and prewarping every sample. Both paths agree exactly at the endpoints, but since `tan` is
+/// convex on `[0 ; pi/2[`, the intermediate values of `g` lie slightly above the exact ones,
+/// i.e. the intermediate cutoffs are slightly higher. For a ramp going from `w_0`
+/// to `w_1`, the largest deviation in `g` is approximately `(w_1 - w_0)^2 * g'' / 8`, where
+/// `g'' = sec^2(w/2) * tan(w/2) / 2` is evaluated around the middle of the ramp.
+/// This is negligible for typical block sizes and modulation rates, but grows quickly
+/// for large jumps close to nyquist.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Ramp<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    current: VFloat<N>,
+    target: VFloat<N>,
+    inc: VFloat<N>,
+    remaining: usize,
+}
+
+impl<const N: usize> Ramp<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a ramp resting at `value`
+    #[inline]
+    pub fn new(value: VFloat<N>) -> Self {
+        Self {
+            current: value,
+            target: value,
+            inc: Simd::splat(0.),
+            remaining: 0,
+        }
+    }
+
+    /// Start moving linearly towards `target`, reaching it after exactly `samples` calls to
+    /// [`Self::tick`]. If `samples == 0`, jump to `target` immediately.
+    #[inline]
+    pub fn set_target(&mut self, target: VFloat<N>, samples: usize) {
+        self.target = target;
+        self.remaining = samples;
+
+        if samples == 0 {
+            self.current = target;
+            self.inc = Simd::splat(0.);
+        } else {
+            self.inc = (target - self.current) / Simd::splat(samples as f32);
+        }
+    }
+
+    /// Jump to `value` immediately, cancelling any ongoing ramp
+    #[inline]
+    pub fn set(&mut self, value: VFloat<N>) {
+        self.set_target(value, 0);
+    }
+
+    /// Advance the ramp by one sample, and return the new current value
+    #[inline]
+    pub fn tick(&mut self) -> VFloat<N> {
+        match self.remaining {
+            0 => {}
+            // land exactly on the target, to avoid accumulating rounding errors
+            1 => {
+                self.remaining = 0;
+                self.current = self.target;
+            }
+            _ => {
+                self.remaining -= 1;
+                self.current += self.inc;
+            }
+        }
+        self.current
+    }
+
+    /// Get the current value, without advancing the ramp
+    #[inline]
+    pub fn current(&self) -> &VFloat<N> {
+        &self.current
+    }
+
+    /// Get the value the ramp is moving towards
+    #[inline]
+    pub fn target(&self) -> &VFloat<N> {
+        &self.target
+    }
+
+    /// Returns `true` if the ramp has reached its target
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Exponential (one-pole) smoothing of a value, e.g. a coefficient set once per buffer,
+/// advanced once per sample, to remove the zipper noise of abrupt changes.
+///
+/// Unlike [`Ramp`], it never quite reaches its target, and doesn't need to be told how many
+/// samples to take: each [`tick`](Self::tick) covers a fixed fraction of the remaining
+/// distance, set from a time constant, and new targets can be set at any time, the motion
+/// staying continuous. Smoothing `g` or `theta`, rather than the cutoff frequency, comes
+/// with a (small, for short time constants) frequency warping error, as described in
+/// [`Ramp`]'s documentation.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Smoothed<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    current: VFloat<N>,
+    target: VFloat<N>,
+    coeff: VFloat<N>,
+}
+
+impl<const N: usize> Default for Smoothed<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(Simd::splat(0.))
+    }
+}
+
+impl<const N: usize> Smoothed<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a smoother resting at `value`, with no smoothing (it jumps to its target)
+    /// until [`Self::set_time`] is called
+    #[inline]
+    pub fn new(value: VFloat<N>) -> Self {
+        Self {
+            current: value,
+            target: value,
+            coeff: Simd::splat(1.),
+        }
+    }
+
+    /// Set the time constant, in each lane, in seconds: the time it takes to cover
+    /// `1 - 1/e` (about `63%`) of the distance to the target, `99%` taking about `4.6` times
+    /// as long. A time of `0` disables smoothing.
+    #[inline]
+    pub fn set_time(&mut self, seconds: VFloat<N>, sample_rate: f32) {
+        // 1 - e^(-1 / (seconds * sample_rate)), with e^x = 2^(x * log2(e))
+        let samples = seconds * Simd::splat(sample_rate);
+        let decay = (Simd::splat(-core::f32::consts::LOG2_E) / samples).exp2();
+        self.coeff = Simd::splat(1.) - decay;
+    }
+
+    /// Start moving towards `target`
+    #[inline]
+    pub fn set_target(&mut self, target: VFloat<N>) {
+        self.target = target;
+    }
+
+    /// Jump to `value` immediately
+    #[inline]
+    pub fn set(&mut self, value: VFloat<N>) {
+        self.current = value;
+        self.target = value;
+    }
+
+    /// Advance by one sample, and return the new current value
+    #[inline]
+    pub fn tick(&mut self) -> VFloat<N> {
+        self.current = self.coeff.mul_add(self.target - self.current, self.current);
+        self.current
+    }
+
+    /// Get the current value, without advancing
+    #[inline]
+    pub fn current(&self) -> &VFloat<N> {
+        &self.current
+    }
+
+    /// Get the value being moved towards
+    #[inline]
+    pub fn target(&self) -> &VFloat<N> {
+        &self.target
+    }
+}
+
+/// Coefficients that can be moved linearly from one value to another, one sample at a time,
+/// as done by [`Ramp`] for a single vector, and by [`BlockSmoothed`](crate::wrappers::BlockSmoothed)
+/// for a filter's whole set of coefficients. The same frequency warping error applies.
+pub trait LinearCoeffs: Copy {
+    /// The per-sample increment moving `from` to `to` in `steps` samples
+    fn increment(from: &Self, to: &Self, steps: usize) -> Self;
+
+    /// Add the increment `inc` to `self`
+    fn advance(&mut self, inc: &Self);
+}
+
+impl<const N: usize> LinearCoeffs for VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn increment(from: &Self, to: &Self, steps: usize) -> Self {
+        (to - from) / Simd::splat(steps as f32)
+    }
+
+    #[inline]
+    fn advance(&mut self, inc: &Self) {
+        *self += inc;
+    }
+}
@@ -1,25 +1,340 @@
 use super::*;
 
+use core::f32::consts::PI;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+/// Compute the integrator pre-gain `g` (see [`SVF::process`]) from a cutoff
+/// frequency `freq`, in Hz.
+///
+/// The cutoff is clamped to `[0 ; 0.999 * nyquist]`, as `g` goes to infinity at nyquist.
+/// Sweeping it past nyquist, e.g. with an envelope, thus holds the filter there, at `g` =
+/// `tan(0.999 * pi / 2)`, about `637`, which is stable, instead of wrapping around, or
+/// producing infinities or NaNs. Negative (and NaN) frequencies are treated as `0`.
+#[inline]
+pub fn g_from_hz<const N: usize>(freq: VFloat<N>, sample_rate: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    prewarp(freq * Simd::splat(2. * PI / sample_rate))
+}
+
+/// Compute the integrator pre-gain `g` (see [`SVF::process`]) from a normalized angular
+/// cutoff frequency `w_c`, in radians per sample, i.e. `tan(w_c / 2)`.
+///
+/// Like [`g_from_hz`], `w_c` is clamped to `[0 ; 0.999 * pi]`.
+#[inline]
+pub fn prewarp<const N: usize>(w_c: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    math::tan_half_x(w_c.simd_clamp(Simd::splat(0.), Simd::splat(0.999 * PI)))
+}
+
+/// Inverse of [`prewarp`]: the normalized angular cutoff frequency, in radians per sample,
+/// actually achieved with the pre-gain `g`, i.e. `2 * atan(g)`.
+///
+/// Multiply by `sample_rate / (2 * pi)` to get it in Hz.
+#[inline]
+pub fn unwarp<const N: usize>(g: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::from_array(g.to_array().map(|g| 2. * g.atan()))
+}
+
+/// Value of `g` at the highest cutoff frequency `g_from_hz` clamps to: `tan(0.999 * pi / 2)`
+const MAX_G: f32 = 636.619_25;
+
+/// Highest normalized cutoff frequency (in cycles per sample) covered by [`GTable`],
+/// consistent with the clamping done by [`g_from_hz`]
+const G_TABLE_MAX_FREQ: f32 = 0.999 * 0.5;
+
+/// Precomputed table of values of `g` (see [`SVF::process`]), for computing `g` from a cutoff
+/// frequency with a cheap linear interpolation instead of a `tan`.
+///
+/// Entries are spaced evenly between DC and just below nyquist (the same range `g_from_hz` clamps
+/// to). The relative error of [`GTable::g_lookup`] grows with the cutoff frequency, as `tan`
+/// gets steeper. With `SIZE = 1024` it stays under:
+///
+/// - `1.2e-6` below `sample_rate / 4`
+/// - `2.4e-5` below `0.45 * sample_rate`
+/// - `6e-4` below `0.49 * sample_rate`
+/// - `4e-2` below `0.499 * sample_rate`
+///
+/// It scales with the square of the spacing between entries, i.e. quadrupling the size
+/// of the table divides it by about 16.
+#[derive(Clone)]
+pub struct GTable<const SIZE: usize = 1024> {
+    table: [f32; SIZE],
+}
+
+impl<const SIZE: usize> Default for GTable<SIZE> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> GTable<SIZE> {
+    pub fn new() -> Self {
+        assert!(SIZE >= 2, "a GTable must have at least 2 entries");
+        let step = G_TABLE_MAX_FREQ / (SIZE - 1) as f32;
+        Self {
+            table: core::array::from_fn(|i| (PI * step * i as f32).tan()),
+        }
+    }
+
+    /// Look up an approximation of `g` for the cutoff frequency `cutoff_hz`, in Hz.
+    ///
+    /// Like [`g_from_hz`], the cutoff is clamped to `[0 ; 0.999 * nyquist]`.
+    #[inline]
+    pub fn g_lookup<const N: usize>(&self, cutoff_hz: VFloat<N>, sample_rate: f32) -> VFloat<N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        let last = (SIZE - 1) as f32;
+        let pos = (cutoff_hz * Simd::splat(last / (G_TABLE_MAX_FREQ * sample_rate)))
+            .simd_clamp(Simd::splat(0.), Simd::splat(last));
+
+        // the last interval is [SIZE - 2 ; SIZE - 1], so that i + 1 is always in bounds
+        let i = pos.floor().simd_min(Simd::splat(last - 1.));
+        let frac = pos - i;
+        let i = i.cast::<usize>();
+
+        let g0 = Simd::gather_or_default(&self.table, i);
+        let g1 = Simd::gather_or_default(&self.table, i + Simd::splat(1));
+        frac.mul_add(g1 - g0, g0)
+    }
+}
+
+/// Compute the resonance `res` (see [`SVF::process`]) corresponding to the quality factor `q`:
+/// `res = 1 / q`.
+///
+/// The prototype's denominator is then `s^2 + s / q + 1`, the same as that of the RBJ
+/// cookbook's filters, so their Q values can be used as is. In the [`trnasfer`] module, the
+/// denominator is written `s^2 + 2 * r * s + 1`, i.e. with `r = res / 2 = 1 / (2 * q)`.
+///
+/// Any `q > 0` gives a stable filter. In particular, `q < 0.5` gives `res > 2`, an overdamped
+/// filter (with two distinct real poles), which is stable, and useful for gentle slopes. The
+/// result is clamped to `[0 ; f32::MAX]`, the range accepted by [`stable_lanes`]: negative
+/// (and NaN) values of `q` give `res = 0`, i.e. an undamped, self-oscillating filter, at the
+/// edge of stability, and `q = 0` the largest finite `res`, rather than infinity.
+#[inline]
+pub fn res_from_q<const N: usize>(q: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // simd_max returns the non-NaN operand
+    q.recip()
+        .simd_max(Simd::splat(0.))
+        .simd_min(Simd::splat(f32::MAX))
+}
+
+/// Compute the resonance `res` (see [`SVF::process`]) giving the bandpass (and peaking, and
+/// notch) outputs a bandwidth of `bw_octaves` octaves, between their `-3 dB` points (or
+/// midpoint gain, for the peaking shapes), around the center frequency `w_c`, in radians per
+/// sample, in `[0 ; pi[`, in each lane.
+///
+/// In the analog domain, a bandwidth of `bw` octaves corresponds to `1 / q = 2 * sinh(ln(2) /
+/// 2 * bw)` (see [`res_from_q`]). The bilinear transform compresses the band's edges as they
+/// approach nyquist, which is compensated for, as in the RBJ cookbook, by scaling `bw` by
+/// `w_c / sin(w_c)`: `res = 2 * sinh(ln(2) / 2 * bw * w_c / sin(w_c))`. This is accurate at
+/// low frequencies, and within a few percent as long as the band's upper edge stays well
+/// below nyquist (e.g. `2.6 %` for one octave around `w_c = 2`), but the bandwidth can't be
+/// preserved once it would cross nyquist.
+///
+/// As with `res_from_q`, the result is clamped to `[0 ; f32::MAX]`, the range accepted by
+/// [`stable_lanes`]: a bandwidth of `0` (or less, or NaN) gives `res = 0`, i.e. a
+/// self-oscillating filter, and very wide bands (or `w_c` very close to nyquist) the
+/// largest finite `res`.
+#[inline]
+pub fn res_from_bandwidth<const N: usize>(bw_octaves: VFloat<N>, w_c: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let res = Simd::from_array(core::array::from_fn(|i| {
+        let w = w_c[i];
+        // w / sin(w) -> 1 as w -> 0
+        let ratio = if w > 1e-4 { w / w.sin() } else { 1. };
+        2. * (core::f32::consts::LN_2 * 0.5 * bw_octaves[i] * ratio).sinh()
+    }));
+
+    // simd_max returns the non-NaN operand
+    res.simd_max(Simd::splat(0.))
+        .simd_min(Simd::splat(f32::MAX))
+}
+
+/// Compute a cramping-corrected integrator pre-gain `g` (see [`SVF::process`]) placing the
+/// resonant peak of the digital lowpass exactly at the frequency of the analog prototype's, in
+/// each lane. `res` is unchanged, and must be the value passed to `process`.
+///
+/// [`g_from_hz`] prewarps the cutoff frequency, so the digital filter matches the analog one
+/// exactly there, and the frequencies of the rest of the response are compressed towards
+/// nyquist (cramping). With resonance, the lowpass peaks slightly below the cutoff, at
+/// `cutoff_hz * sqrt(1 - res^2 / 2)` in the analog domain. Since that is where the resonance
+/// is heard, this prewarps that frequency instead, i.e. `g = tan(pi * f_peak / sample_rate)
+/// / sqrt(1 - res^2 / 2)`, the digital peak being where `tan(w/2) = g * sqrt(1 - res^2 / 2)`.
+///
+/// This only differs noticeably from `g_from_hz` for high cutoffs and low to moderate
+/// resonance (e.g., at `48 kHz`, with `res = 0.5` and a cutoff at `15 kHz`, the uncorrected
+/// peak is at `14.5 kHz`, instead of `14.0 kHz`). In exchange, the cutoff itself (and the
+/// bandpass' peak) ends up slightly below `cutoff_hz`. The peak gain is preserved. When
+/// `res >= sqrt(2)`, the lowpass has no resonant peak, and this is the same as `g_from_hz`.
+#[inline]
+pub fn g_from_hz_peak_corrected<const N: usize>(
+    cutoff_hz: VFloat<N>,
+    res: VFloat<N>,
+    sample_rate: f32,
+) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let ratio2 = (res * res).mul_add(Simd::splat(-0.5), Simd::splat(1.));
+    let resonant = ratio2.simd_gt(Simd::splat(0.));
+
+    // avoid NaNs in non-resonant lanes
+    let ratio = resonant.select(ratio2, Simd::splat(1.)).sqrt();
+    let g = g_from_hz(cutoff_hz * ratio, sample_rate) / ratio;
+
+    g.simd_min(Simd::splat(MAX_G))
+}
+
+/// Round `cutoff_hz` to the nearest equal-tempered semitone relative to `reference_hz`
+/// (e.g. `440.0` for A4-based tuning), in each lane, returning a frequency in Hz, e.g. to keep
+/// a self-oscillating filter, used as a tone generator, in tune. Both must be positive.
+///
+/// This only affects the cutoff, the result is meant to be passed to [`g_from_hz`],
+/// with `res` left as is.
+#[inline]
+pub fn snap_cutoff_to_semitone<const N: usize>(cutoff_hz: VFloat<N>, reference_hz: f32) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let semitones = (cutoff_hz * Simd::splat(reference_hz.recip())).log2() * Simd::splat(12.);
+    Simd::splat(reference_hz) * (semitones.round() * Simd::splat(1. / 12.)).exp2()
+}
+
+/// Returns a mask of the lanes where `g` and `res` lie in the range
+/// in which [`SVF::process`] can't blow up: both must be finite and positive (or `0`).
+///
+/// The filter is stable for a strictly positive `res`. `res = 0` is accepted, but removes all
+/// damping: the filter is then only marginally stable, ringing forever at its cutoff instead
+/// of decaying, and growing without bound when driven there.
+#[inline]
+pub fn stable_lanes<const N: usize, T: FloatElement>(
+    g: Simd<T, N>,
+    res: Simd<T, N>,
+) -> Mask<T::Mask, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
+{
+    let zero = splat(0.);
+    g.is_finite() & g.simd_ge(zero) & res.is_finite() & res.simd_ge(zero)
+}
+
+/// Lower `base_res` (i.e. increase the resonance) as the cutoff rises, in each lane, to model
+/// the resonance bump some analog filters develop at high cutoffs.
+///
+/// The cutoff is measured by `t = g / (1 + g)`, which goes from `0` at DC, through `0.5` at a
+/// quarter of the sample rate, to `1` at nyquist, and the result is `base_res * (1 - tracking
+/// * t)`. `tracking = 0` leaves `base_res` untouched, `tracking = 1` removes all damping at
+/// nyquist. The result is clamped to `[0 ; base_res]`, i.e. to the range accepted by
+/// [`stable_lanes`], even for `tracking > 1` or a negative `g`.
+///
+/// `g` must be the value passed alongside the result to [`SVF::process`].
+#[inline]
+pub fn res_with_tracking<const N: usize>(
+    base_res: VFloat<N>,
+    g: VFloat<N>,
+    tracking: VFloat<N>,
+) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let one = Simd::splat(1.);
+    let g = g.simd_max(Simd::splat(0.));
+    let t = g / (one + g);
+    let scale = (-tracking).mul_add(t, one).simd_clamp(Simd::splat(0.), one);
+    base_res * scale
+}
+
+/// Clamp `g` and `res` into the range accepted by [`stable_lanes`], mapping NaNs to `0.0`.
+///
+/// Kept out of line, so that it doesn't weigh on the inlined `process_clamped`,
+/// which only calls it when some coefficients are out of range.
+#[cold]
+#[inline(never)]
+fn clamp_coeffs<const N: usize>(g: VFloat<N>, res: VFloat<N>) -> (VFloat<N>, VFloat<N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // simd_max returns the non-NaN operand
+    let zero = Simd::splat(0.);
+    (
+        g.simd_max(zero).simd_min(Simd::splat(MAX_G)),
+        res.simd_max(zero).simd_min(Simd::splat(f32::MAX)),
+    )
+}
+
+/// Soft limiter, leaving `|x| <= ceiling / 2` untouched, and smoothly (with a continuous
+/// slope) compressing larger values, with [`nonlin::saturate`] applied to the excess over
+/// that knee, so that they approach, but never exceed, `ceiling`
+#[inline]
+fn soft_limit<const N: usize>(x: VFloat<N>, ceiling: VFloat<N>) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let knee = ceiling * Simd::splat(0.5);
+    let a = x.abs();
+    let over = (a - knee).simd_max(Simd::splat(0.));
+    let soft = knee * nonlin::saturate(over, knee.recip());
+    (a.simd_min(knee) + soft).copysign(x)
+}
+
 /// Digital implementation of the analogue SVF Filter. Based on the
 /// one in the book The Art of VA Filter Design by Vadim Zavalishin
 ///
 /// Capable of outputing many different shapes,
 /// (highpass, lowpass, bandpass, allpass, notch, shelving....)
-#[derive(Default)]
-pub struct SVF<const N: usize = FLOATS_PER_VECTOR>
+///
+/// The lanes are `f32` by default. As with [`OnePole`](crate::one_pole::OnePole), `f64`
+/// ones (see [`FloatElement`]) only get the core methods.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct SVF<const N: usize = FLOATS_PER_VECTOR, T: FloatElement = f32>
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    x: VFloat<N>,
-    hp: VFloat<N>,
-    bp: Integrator<N>,
-    bp1: VFloat<N>,
-    lp: Integrator<N>,
+    x: Simd<T, N>,
+    hp: Simd<T, N>,
+    bp: Integrator<N, T>,
+    bp1: Simd<T, N>,
+    lp: Integrator<N, T>,
 }
 
-impl<const N: usize> SVF<N>
+/// Prints the integrator states, followed by the cached outputs, as plain per-lane arrays
+impl<const N: usize, T: FloatElement> core::fmt::Debug for SVF<N, T>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SVF")
+            .field("bp_state", &self.bp.state().to_array())
+            .field("lp_state", &self.lp.state().to_array())
+            .field("x", &self.x.to_array())
+            .field("hp", &self.hp.to_array())
+            .field("bp", &self.bp.output().to_array())
+            .field("lp", &self.lp.output().to_array())
+            .finish()
+    }
+}
+
+impl<const N: usize, T: FloatElement> SVF<N, T>
 where
     LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: FloatVector<T, N>,
 {
     #[inline]
     pub fn reset(&mut self) {
@@ -28,6 +343,65 @@ where
         }
     }
 
+    /// Set the internal states such that the filter settles at its steady-state response to
+    /// the constant input `x` immediately, i.e. as if it had been fed `x` for a very long
+    /// time: the lowpass output is `x`, the highpass and bandpass outputs are `0.0`.
+    ///
+    /// For a constant input, every integrator's input is zero, so its state equals its output:
+    /// the bandpass integrator's state is `0.0`, and the lowpass integrator's, `x`. This holds
+    /// regardless of the coefficients. See also [`OnePole::reset_to`](crate::one_pole::OnePole::reset_to).
+    #[inline]
+    pub fn reset_to(&mut self, x: Simd<T, N>) {
+        self.bp.reset();
+        self.lp.set_state(x);
+        // with g = 0, the states are left untouched, only the outputs are updated
+        self.process(x, splat(0.), splat(0.));
+    }
+
+    /// Set the cached outputs to `0.0`, leaving the integrator states untouched.
+    ///
+    /// The opposite of [`Self::reset`], useful after restoring the states, when the outputs
+    /// computed from the previous ones are meaningless. They are recomputed on the next
+    /// `process` call.
+    #[inline]
+    pub fn flush_outputs(&mut self) {
+        let zero = splat(0.);
+        self.x = zero;
+        self.hp = zero;
+        self.bp1 = zero;
+        for i in [&mut self.bp, &mut self.lp] {
+            i.flush_output();
+        }
+    }
+
+    /// Blend this filter's integrator states towards `other`'s, by `t` (`0` keeps this
+    /// filter's, `1` takes `other`'s), in each lane. The cached outputs are left untouched.
+    ///
+    /// Meant for smoothly handing off between two filters when morphing between patches:
+    /// e.g. processing both during a short crossfade, while gradually pulling the first
+    /// one's state towards the second's. This is heuristic: a blend of two valid states is
+    /// only a valid state for the target coefficients if both filters have similar
+    /// coefficients, otherwise, expect a (reduced) transient.
+    #[inline]
+    pub fn lerp_state(&mut self, other: &Self, t: Simd<T, N>) {
+        for (this, other) in [(&mut self.bp, &other.bp), (&mut self.lp, &other.lp)] {
+            let &s = this.state();
+            this.set_state(t.mul_add(other.state() - s, s));
+        }
+    }
+
+    /// Sum of the squares of the integrator states, in each lane.
+    ///
+    /// A cheap measure of how much energy is stored in the filter, e.g. for a "ringing"
+    /// indicator. A lane with a near-zero state energy, fed silence, outputs silence, making
+    /// this also usable as a voice activity detector.
+    #[inline]
+    pub fn state_energy(&self) -> Simd<T, N> {
+        let &bp_s = self.bp.state();
+        let &lp_s = self.lp.state();
+        bp_s.mul_add(bp_s, lp_s * lp_s)
+    }
+
     /// Update the filter's internal state.
     ///
     /// This should be called _only once_ per sample, _every sample_
@@ -40,17 +414,35 @@ where
     /// `g` is the integrator pre-gain: If `0 <= w_c < pi` is the cutoff frequency,
     /// in radians per sample, then, `g = tan(w_c/2)`.
     ///
-    /// `res` is the resonance value of the filter. `0 <= res < 2` must hold.
-    /// Values outside of that range may result in instability.
+    /// `res` is the resonance value of the filter, the inverse of its quality factor
+    /// (see [`res_from_q`]). `res >= 0` must hold. `res = 0` makes the filter self-oscillate,
+    /// `res < sqrt(2)` gives a resonant peak to the lowpass and highpass outputs, and `res > 2`
+    /// an overdamped response.
+    ///
+    /// Negative values are unstable: they flip the sign of the damping term of the prototype's
+    /// denominator `s^2 + res * s + 1`, placing its poles in the right half-plane (growing
+    /// oscillations), which the bilinear transform maps outside the unit circle, so no amount
+    /// of negative resonance can be used safely. To flatten or dip the resonant peak, use a
+    /// larger `res` instead.
+    ///
+    /// In debug builds, this panics if `g` or `res` is out of range (see [`stable_lanes`]),
+    /// in any lane. See [`Self::process_clamped`] to clamp them instead.
     #[inline]
-    pub fn process(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) {
+    pub fn process(&mut self, x: Simd<T, N>, g: Simd<T, N>, res: Simd<T, N>) {
+        debug_assert!(
+            stable_lanes(g, res).all(),
+            "unstable SVF coefficients, g: {:?}, res: {:?}",
+            g.to_array(),
+            res.to_array(),
+        );
+
         self.x = x;
         let &bp_s = self.bp.state();
         let &lp_s = self.lp.state();
 
         let g1 = res + g;
 
-        self.hp = g1.mul_add(-bp_s, self.x - lp_s) / g1.mul_add(g, Simd::splat(1.));
+        self.hp = g1.mul_add(-bp_s, self.x - lp_s) / g1.mul_add(g, splat(1.));
 
         self.bp.process(self.hp * g);
         let &bp = self.bp.output();
@@ -58,45 +450,118 @@ where
         self.lp.process(bp * g);
     }
 
+    /// Get the three canonical taps of the filter, `(hp, bp, lp)`, exactly as computed by the
+    /// last `process` call (the same values [`Self::get_highpass`], [`Self::get_bandpass`] and
+    /// [`Self::get_lowpass`] return).
+    ///
+    /// With `res` the damping passed to `process`, and `x` the input, every other output is a
+    /// linear combination of these: `x = hp + res * bp + lp`, the unit bandpass is `res * bp`,
+    /// the notch is `hp + lp`, the allpass is `lp - res * bp + hp` (negated w.r.t.
+    /// [`Self::get_allpass`]), and the shelves weigh `hp`, `res * bp` and `lp` by powers of
+    /// their gain. These relations are a stable API, only the derived getters may change.
+    #[inline]
+    pub fn raw_taps(&self) -> (Simd<T, N>, Simd<T, N>, Simd<T, N>) {
+        (self.hp, *self.bp.output(), *self.lp.output())
+    }
+
     #[inline]
-    pub fn get_passthrough(&self) -> &VFloat<N> {
+    pub fn get_passthrough(&self) -> &Simd<T, N> {
         &self.x
     }
 
+    /// Lowpass output.
+    ///
+    /// Its DC gain is exactly `1`, for all values of `res`: at `z = 1`, the prototype is
+    /// evaluated at `s = 0`, where `1 / (s^2 + res * s + 1) = 1`. No corrective gain is thus
+    /// needed. In practice, with a constant input, the output converges towards the input, until
+    /// the lowpass integrator's updates (`g` times the bandpass output) fall below its state's
+    /// precision, and it stalls, which happens for small values of `g`. For instance, with
+    /// `res = sqrt(2)`, the output settles within a relative error of about `3e-6` of the
+    /// input at `g = 0.01` (around `75 Hz` at `48 kHz`), and `7e-5` at `g = 0.001`, and this
+    /// error grows with `res`. This is a limit of single precision (`f32` lanes), not a bias.
     #[inline]
-    pub fn get_lowpass(&self) -> &VFloat<N> {
+    pub fn get_lowpass(&self) -> &Simd<T, N> {
         self.lp.output()
     }
 
+    /// Lowpass output, with the rise of the passband towards the resonant peak compensated,
+    /// by mixing the highpass output back in. `res` must be the value passed to `process`.
+    ///
+    /// The prototype lowpass' squared magnitude is `1 / ((1 - w^2)^2 + res^2 * w^2)`, with
+    /// `w` the frequency relative to the cutoff. Below the cutoff, when `res < sqrt(2)`, this
+    /// rises monotonically from `1` at DC, as `1 + (2 - res^2) * w^2 + ...`. Returning
+    /// `lp + k * hp`, i.e. changing the numerator to `(1 - k * w^2)^2`, with
+    /// `k = 1 - res^2 / 2` (clamped to `0`), cancels the `w^2` term, making the passband
+    /// maximally flat, with a squared magnitude of `1 + O(w^4)`.
+    ///
+    /// This comes at a cost: the numerator has a zero at `w = 1 / sqrt(k)`, placing a notch
+    /// just above the cutoff, which swallows most of the resonant peak, and the gain above
+    /// it tends to `k`, instead of rolling off. E.g. with `res = 0.3`, the raw lowpass is
+    /// `+2.3 dB` and `+7.1 dB` above its DC gain one octave, and a third of an octave below the
+    /// cutoff, where this output is at `-0.04 dB` and `-0.9 dB`. For `res >= sqrt(2)`, there
+    /// is no peak, and this is the raw lowpass.
+    #[inline]
+    pub fn get_lowpass_compensated(&self, res: Simd<T, N>) -> Simd<T, N> {
+        let k = (res * res).mul_add(splat(-0.5), splat(1.));
+        k.simd_max(splat(0.))
+            .mul_add(*self.get_highpass(), *self.get_lowpass())
+    }
+
+    /// Bandpass output, with a peak gain of `1 / res`, at the cutoff frequency, which grows
+    /// with resonance. See [`Self::get_unit_bandpass`] for a resonance-independent level.
     #[inline]
-    pub fn get_bandpass(&self) -> &VFloat<N> {
+    pub fn get_bandpass(&self) -> &Simd<T, N> {
         self.bp.output()
     }
 
+    /// Bandpass output, normalized to a peak gain of exactly `1` (`0 dB`), for all values of
+    /// `res`, so that the resonance only controls the bandwidth, and not the loudness.
+    ///
+    /// The prototype bandpass is `H(s) = s / (s^2 + res * s + 1)`, whose magnitude peaks at
+    /// `s = j` (the cutoff) with `|H(j)| = |j / (res * j)| = 1 / res`. Scaling it by `res`
+    /// thus gives a unit peak. Since the bilinear transform maps the digital frequency `w`
+    /// to the analog one `tan(w/2) / g`, the digital response also peaks at `1`, where
+    /// `tan(w/2) = g`, i.e. exactly at `w_c`.
     #[inline]
-    pub fn get_unit_bandpass(&self) -> &VFloat<N> {
+    pub fn get_unit_bandpass(&self) -> &Simd<T, N> {
         &self.bp1
     }
 
+    /// Bandpass output, normalized such that the amplitude of its ringing (e.g. when
+    /// self-oscillating) is independent of the cutoff, for use as a sine source. `g` must be
+    /// the (strictly positive) value passed to [`Self::process`].
+    ///
+    /// Unlike the peak gain of the bandpass (`1 / res`, see [`Self::get_unit_bandpass`]),
+    /// which doesn't depend on the cutoff, the amplitude of the ringing following a given
+    /// excitation does: with `res = 0`, a unit impulse makes the filter oscillate with an
+    /// amplitude of exactly `sin(w_c) = 2 * g / (1 + g^2)`, in all three outputs, vanishing
+    /// towards DC and nyquist. This divides the bandpass output by it, so that a unit
+    /// impulse always gives a unit oscillation. Below self-oscillation, the ringing starts
+    /// with roughly the same amplitude, then decays, increasingly fast as `res` grows.
+    #[inline]
+    pub fn get_bandpass_normalized(&self, g: Simd<T, N>) -> Simd<T, N> {
+        self.bp.output() * g.mul_add(g, splat(1.)) / (g + g)
+    }
+
     #[inline]
-    pub fn get_highpass(&self) -> &VFloat<N> {
+    pub fn get_highpass(&self) -> &Simd<T, N> {
         &self.hp
     }
 
     #[inline]
-    pub fn get_allpass(&self) -> VFloat<N> {
+    pub fn get_allpass(&self) -> Simd<T, N> {
         // 2 * bp1 - x
-        self.get_unit_bandpass().mul_add(Simd::splat(2.), -self.x)
+        self.get_unit_bandpass().mul_add(splat(2.), -self.x)
     }
 
     #[inline]
-    pub fn get_notch(&self) -> VFloat<N> {
+    pub fn get_notch(&self) -> Simd<T, N> {
         // x - bp1
         self.get_passthrough() - self.get_unit_bandpass()
     }
 
     #[inline]
-    pub fn get_high_shelf(&self, root_gain: VFloat<N>) -> VFloat<N> {
+    pub fn get_high_shelf(&self, root_gain: Simd<T, N>) -> Simd<T, N> {
         let &hp = self.get_highpass();
         let &bp1 = self.get_unit_bandpass();
         let &lp = self.get_lowpass();
@@ -104,90 +569,2424 @@ where
     }
 
     #[inline]
-    pub fn get_band_shelf(&self, root_gain: VFloat<N>) -> VFloat<N> {
+    pub fn get_band_shelf(&self, root_gain: Simd<T, N>) -> Simd<T, N> {
         let &bp1 = self.get_unit_bandpass();
         let &x = self.get_passthrough();
         bp1.mul_add(root_gain, x - bp1)
     }
 
+    /// Peaking (bell) output, `x + (gain - 1) * bp1`, boosting (or cutting) the frequencies
+    /// around the cutoff by `gain` (linear, not the square root, unlike the low and high
+    /// shelves). This is the same output as [`Self::get_band_shelf`], under its usual EQ name.
+    ///
+    /// Its response is [`trnasfer::peaking`]: `(s^2 + gain * res * s + 1) / (s^2 + res * s +
+    /// 1)`, at its extremum, `gain`, at the cutoff (for any `res`). With a constant `res`,
+    /// cuts are narrower than boosts. For symmetric boosts and cuts, i.e. a cut by `gain`
+    /// exactly undoing a boost by `1 / gain`, pass `res / sqrt(gain)` to `process`
+    /// instead of `res`, which gives the response of [`trnasfer::band_shelf`].
+    #[inline]
+    pub fn get_peaking(&self, gain: Simd<T, N>) -> Simd<T, N> {
+        self.get_band_shelf(gain)
+    }
+
     #[inline]
-    pub fn get_low_shelf(&self, root_gain: VFloat<N>) -> VFloat<N> {
+    pub fn get_low_shelf(&self, root_gain: Simd<T, N>) -> Simd<T, N> {
         let &hp = self.get_highpass();
         let &bp1 = self.get_unit_bandpass();
         let &lp = self.get_lowpass();
         root_gain.mul_add(root_gain.mul_add(lp, bp1), hp)
     }
+
+    /// Continuous crossfade from the lowpass (`morph = 0`), through the unit bandpass
+    /// (`morph = 0.5`), to the highpass (`morph = 1`), per lane. `morph` is clamped to
+    /// `[0 ; 1]`.
+    ///
+    /// The unit bandpass is used, rather than the raw bandpass, so that the level at the
+    /// cutoff stays the same, whatever the resonance, throughout the sweep. Only two adjacent
+    /// shapes are ever mixed, so the intermediate responses have no notch (unlike a mix of
+    /// the lowpass and highpass, which is exactly the notch output, halfway through).
+    #[inline]
+    pub fn get_morph(&self, morph: Simd<T, N>) -> Simd<T, N> {
+        let (zero, one) = (splat(0.), splat(1.));
+        let m2 = morph + morph;
+        let to_bp = m2.simd_clamp(zero, one);
+        let to_hp = (m2 - one).simd_clamp(zero, one);
+
+        let &lp = self.get_lowpass();
+        let &bp1 = self.get_unit_bandpass();
+        let &hp = self.get_highpass();
+
+        to_hp.mul_add(hp - bp1, to_bp.mul_add(bp1 - lp, lp))
+    }
 }
 
-#[cfg(feature = "num")]
-pub mod trnasfer {
+impl<const N: usize> SVF<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Compute the coefficients that keep the same analog cutoff frequency (in Hz) as `coeffs`
+    /// after a sample rate change from `old_sample_rate` to `new_sample_rate`, i.e. recompute
+    /// `g = tan(pi * cutoff / sample_rate)`. `res` is unchanged.
+    ///
+    /// If `rescale_state` is `true`, the integrator states are also re-seeded so that the
+    /// bandpass and lowpass outputs, which correspond to the analog filter's state variables
+    /// and don't depend on the sample rate, stay continuous across the change. This is not
+    /// exact, as the trapezoidal states also depend on the last integrator inputs, themselves
+    /// computed with the old coefficients, but it greatly reduces the transient, compared to
+    /// leaving them untouched.
+    #[inline]
+    pub fn retune(
+        &mut self,
+        old_sample_rate: f32,
+        new_sample_rate: f32,
+        coeffs: &SvfCoeffs<N>,
+        rescale_state: bool,
+    ) -> SvfCoeffs<N> {
+        let g = scale_prewarped(coeffs.g, old_sample_rate / new_sample_rate);
 
-    use super::*;
+        if rescale_state {
+            let &hp = self.get_highpass();
+            let &bp = self.get_bandpass();
+            let &lp = self.get_lowpass();
+            self.bp.set_state(g.mul_add(hp, bp));
+            self.lp.set_state(g.mul_add(bp, lp));
+        }
+
+        SvfCoeffs::new(g, coeffs.res)
+    }
 
+    /// Returns `true` if, in every lane, both integrator states of this filter
+    /// are within `epsilon` of `other`'s
     #[inline]
-    fn two<T: Float>(res: T) -> T {
-        res + res
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.bp.approx_eq(&other.bp, epsilon) && self.lp.approx_eq(&other.lp, epsilon)
     }
 
+    /// Filter `buf` in place, replacing each sample with the output selected by `shape`, while
+    /// applying timestamped coefficient changes, as delivered by plugin hosts.
+    ///
+    /// `coeffs` holds the coefficients in effect at the start of the buffer, each entry
+    /// `(offset, new_coeffs)` of `events` replaces them from the sample at index `offset`
+    /// onwards, and, on return, `coeffs` holds those in effect at the end of the buffer,
+    /// to be passed to the next call. `events` must be sorted by offset. Events with offsets
+    /// past the end of the buffer are still applied, as if they happened at its end.
     #[inline]
-    fn h_denominator<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        s * (s + two(res)) + T::one()
+    pub fn process_events(
+        &mut self,
+        buf: &mut [VFloat<N>],
+        coeffs: &mut SvfCoeffs<N>,
+        events: &[(usize, SvfCoeffs<N>)],
+        shape: FilterShape<VFloat<N>>,
+    ) {
+        let mut events = events.iter().peekable();
+
+        for (i, sample) in buf.iter_mut().enumerate() {
+            while let Some(&(_, new_coeffs)) = events.next_if(|&&(offset, _)| offset <= i) {
+                *coeffs = new_coeffs;
+            }
+
+            self.process(*sample, coeffs.g, coeffs.res);
+            *sample = Filter::get(self, shape);
+        }
+
+        if let Some(&(_, new_coeffs)) = events.last() {
+            *coeffs = new_coeffs;
+        }
     }
 
+    /// Filter `input`, with constant coefficients, writing, for each sample, the value returned
+    /// by `select` given all of the filter's outputs (see [`Self::outputs`]) to `out`.
+    ///
+    /// This allows arbitrary combinations of the outputs at block speed, `select` being
+    /// monomorphized (and usually inlined), there's no dynamic dispatch involved.
+    ///
+    /// # Panics
+    ///
+    /// If `input` and `out` don't have the same length
     #[inline]
-    pub fn low_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        h_denominator(s, res).finv()
+    pub fn process_each(
+        &mut self,
+        input: &[VFloat<N>],
+        coeffs: &SvfCoeffs<N>,
+        out: &mut [VFloat<N>],
+        select: impl Fn(&SvfOutputs<N>) -> VFloat<N>,
+    ) {
+        assert_eq!(
+            input.len(),
+            out.len(),
+            "input and output lengths must match"
+        );
+
+        for (&x, y) in input.iter().zip(out) {
+            self.process(x, coeffs.g, coeffs.res);
+            *y = select(&self.outputs());
+        }
     }
 
+    /// Filter `input` into `out`, taking the output selected by `shape`, but only in the
+    /// samples and lanes where `mask` is set, the others being passed through unfiltered.
+    ///
+    /// In bypassed lanes, if `freeze_when_bypassed` is `false`, the filter keeps processing the
+    /// input as usual, it is only its output that is discarded, so that, when the mask is set
+    /// again, the output resumes as if it had never been bypassed (but the transition from the
+    /// dry to the filtered signal is abrupt). If it is `true`, the state of these lanes is left
+    /// untouched instead (see [`Self::process_gated`]), and filtering resumes exactly where it
+    /// left off, as if the bypassed region was cut out of the input, which is also cheaper to
+    /// recover from if that region contains garbage (e.g. a NaN), but leads to a transient
+    /// if the input changed a lot in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// If `input`, `mask` and `out` don't all have the same length
     #[inline]
-    pub fn band_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        s.fdiv(h_denominator(s, res))
+    pub fn process_block_masked(
+        &mut self,
+        input: &[VFloat<N>],
+        coeffs: &SvfCoeffs<N>,
+        mask: &[Mask<i32, N>],
+        out: &mut [VFloat<N>],
+        shape: FilterShape<VFloat<N>>,
+        freeze_when_bypassed: bool,
+    ) {
+        assert_eq!(
+            input.len(),
+            out.len(),
+            "input and output lengths must match"
+        );
+        assert_eq!(input.len(), mask.len(), "input and mask lengths must match");
+
+        for ((&x, &active), y) in input.iter().zip(mask).zip(out) {
+            if freeze_when_bypassed {
+                self.process_gated(x, coeffs.g, coeffs.res, active);
+            } else {
+                self.process(x, coeffs.g, coeffs.res);
+            }
+            *y = active.select(self.get(shape), x);
+        }
     }
 
+    /// Run the sample `x` through each filter of `filters`, in order, each one with its own
+    /// coefficients and output shape (the entries at the same index in `coeffs` and `shapes`),
+    /// and return the output of the last one, or `x`, if `filters` is empty.
+    ///
+    /// This is the runtime counterpart of [`Cascade`](crate::wrappers::Cascade), whose number of
+    /// stages is fixed, and whose stages all share the same shape, for chains whose length can
+    /// change, e.g. a user-editable stack of EQ bands. Both run on the same loop.
+    ///
+    /// # Panics
+    ///
+    /// If `filters`, `coeffs` and `shapes` don't all have the same length
     #[inline]
-    pub fn unit_band_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        band_pass(s, res).scale(two(res))
+    pub fn process_chain(
+        filters: &mut [Self],
+        coeffs: &[SvfCoeffs<N>],
+        shapes: &[FilterShape<VFloat<N>>],
+        x: VFloat<N>,
+    ) -> VFloat<N> {
+        assert_eq!(
+            filters.len(),
+            coeffs.len(),
+            "filters and coeffs lengths must match"
+        );
+        assert_eq!(
+            filters.len(),
+            shapes.len(),
+            "filters and shapes lengths must match"
+        );
+
+        wrappers::chain(filters, coeffs, shapes.iter().copied(), x)
     }
 
+    /// Same as [`Self::process`], but computed with separate multiplications and additions,
+    /// instead of fused multiply-adds.
+    ///
+    /// `mul_add` always rounds once, so `process` gives the same results everywhere, but,
+    /// on targets without hardware FMA (or when it isn't enabled at compile time, e.g. the
+    /// default `x86_64` target), it is emulated in software, which is much slower, and other
+    /// implementations (e.g. in C, or on a GPU) might not fuse the same operations. This path
+    /// only uses IEEE multiplications, divisions and additions, in a fixed order, reproducible
+    /// by any implementation doing the same, and fast on any target. The results differ
+    /// slightly from those of `process`, due to the extra rounding of the products (by about
+    /// `1e-6`, relative to the signal's level).
     #[inline]
-    pub fn high_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        (s * s).fdiv(h_denominator(s, res))
+    pub fn process_deterministic(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) {
+        debug_assert!(
+            stable_lanes(g, res).all(),
+            "unstable SVF coefficients, g: {:?}, res: {:?}",
+            g.to_array(),
+            res.to_array(),
+        );
+
+        self.x = x;
+        let &bp_s = self.bp.state();
+        let &lp_s = self.lp.state();
+
+        let g1 = res + g;
+
+        self.hp = (self.x - lp_s - g1 * bp_s) / (g1 * g + Simd::splat(1.));
+
+        self.bp.process(self.hp * g);
+        let &bp = self.bp.output();
+        self.bp1 = bp * res;
+        self.lp.process(bp * g);
     }
 
+    /// Same as [`Self::process`], with the same `g` and `res` in every lane
     #[inline]
-    pub fn all_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        let bp1 = unit_band_pass(s, res);
-        bp1 + bp1 - Complex::one()
+    pub fn process_scalar(&mut self, x: VFloat<N>, g: f32, res: f32) {
+        self.process(x, Simd::splat(g), Simd::splat(res));
     }
 
+    /// Same as [`Self::process`], returning the lowpass output, for single-output use
     #[inline]
-    pub fn notch<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
-        Complex::<T>::one() - unit_band_pass(s, res)
+    pub fn process_lowpass(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) -> VFloat<N> {
+        self.process(x, g, res);
+        *self.get_lowpass()
     }
 
+    /// Same as [`Self::process`], returning the bandpass output, for single-output use
     #[inline]
-    pub fn tilting<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
-        let m2 = gain.sqrt();
-        let m = m2.sqrt();
-        let sm = s.unscale(m);
-        (s * s + sm.scale(two(res)) + m2.recip()).fdiv(h_denominator(sm, res))
+    pub fn process_bandpass(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) -> VFloat<N> {
+        self.process(x, g, res);
+        *self.get_bandpass()
     }
 
+    /// Same as [`Self::process`], returning the highpass output, for single-output use
     #[inline]
-    pub fn low_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
-        let m2 = gain.sqrt();
-        tilting(s, res, gain.recip()).scale(m2)
+    pub fn process_highpass(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) -> VFloat<N> {
+        self.process(x, g, res);
+        *self.get_highpass()
     }
 
+    /// Same as [`Self::process`], with the coefficients' derived values precomputed,
+    /// replacing the per-sample division with a multiplication.
+    ///
+    /// The output may differ from that of `process`, with the same coefficients,
+    /// in the last bits, since the reciprocal of the denominator is rounded.
     #[inline]
-    pub fn band_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
-        let m = gain.sqrt();
-        (s * (s + two(res) * m) + T::one()).fdiv(h_denominator(s, res / m))
+    pub fn process_with(&mut self, x: VFloat<N>, coeffs: &PrecomputedSvfCoeffs<N>) {
+        self.x = x;
+        let &bp_s = self.bp.state();
+        let &lp_s = self.lp.state();
+
+        self.hp = coeffs.g1.mul_add(-bp_s, self.x - lp_s) * coeffs.d;
+
+        self.bp.process(self.hp * coeffs.g);
+        let &bp = self.bp.output();
+        self.bp1 = bp * coeffs.res;
+        self.lp.process(bp * coeffs.g);
     }
 
+    /// Same as [`Self::process`], but first clamps `g` and `res` into the range accepted by
+    /// [`stable_lanes`], NaNs being replaced with `0.0`.
+    ///
+    /// Only a range check is performed inline, the clamping itself is done out of line, so
+    /// the cost over `process` is negligible as long as the coefficients are in range.
     #[inline]
-    pub fn high_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
-        let m2 = gain.sqrt();
-        tilting(s, res, gain).scale(m2)
+    pub fn process_clamped(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) {
+        let (g, res) = if stable_lanes(g, res).all() {
+            (g, res)
+        } else {
+            clamp_coeffs(g, res)
+        };
+        self.process(x, g, res);
+    }
+
+    /// Same as [`Self::process`], but first soft-clips the input to `input_ceiling`
+    /// (which must be positive), preventing extreme transients from winding up the
+    /// integrators of a high-Q filter, and ringing for a long time.
+    ///
+    /// Input samples below half of the ceiling, in absolute value, are left untouched. Above
+    /// that, they are progressively compressed, never exceeding the ceiling. This distorts
+    /// large inputs, so the filter no longer behaves linearly (and its response no longer
+    /// matches the transfer functions) for them.
+    #[inline]
+    pub fn process_limited(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+        input_ceiling: VFloat<N>,
+    ) {
+        self.process(soft_limit(x, input_ceiling), g, res);
+    }
+
+    /// Same as [`Self::process`], but the bandpass integrator's state is then soft-clipped
+    /// to `threshold` (which must be positive), bounding the resonance like the saturating
+    /// stages of an analog filter would, in a "squelchy" way, when driven hard.
+    ///
+    /// States below half of the threshold, in absolute value, are left untouched, which
+    /// means that the filter is linear for moderate signals. Above that, they are progressively
+    /// compressed, never exceeding the threshold. The saturation is applied to the state after
+    /// the linear update (i.e. it isn't solved for implicitly), which is cheap, and accurate
+    /// as long as the cutoff is well below nyquist.
+    #[inline]
+    pub fn process_nonlinear(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+        threshold: VFloat<N>,
+    ) {
+        self.process_nonlinear_metered(x, g, res, threshold);
+    }
+
+    /// Same as [`Self::process_nonlinear`], but also returns, in each lane, how hard the
+    /// nonlinearity is being driven: `|state| / threshold`, clamped to `[0 ; 1]`, where
+    /// the state is that of the bandpass integrator before saturation. Saturation starts
+    /// at `0.5`, `1.0` meaning that the state is at, or beyond, the threshold.
+    ///
+    /// Meant for a drive or gain-reduction meter. The outputs themselves
+    /// are available through the getters, as usual.
+    #[inline]
+    pub fn process_nonlinear_metered(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+        threshold: VFloat<N>,
+    ) -> VFloat<N> {
+        self.process(x, g, res);
+        let &s = self.bp.state();
+        self.bp.set_state(soft_limit(s, threshold));
+        (s.abs() / threshold).simd_clamp(Simd::splat(0.), Simd::splat(1.))
+    }
+
+    /// Same as [`Self::process`], but only updates the lanes where `active` is set. In the
+    /// other ones, the internal state and outputs are left frozen, e.g. for idle voices
+    /// of a voice bank, so that they can be resumed exactly where they left off.
+    #[inline]
+    pub fn process_gated(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+        active: Mask<i32, N>,
+    ) {
+        let prev = *self;
+        self.process(x, g, res);
+
+        self.x = active.select(self.x, prev.x);
+        self.hp = active.select(self.hp, prev.hp);
+        self.bp1 = active.select(self.bp1, prev.bp1);
+        self.bp = Integrator::select(active, &self.bp, &prev.bp);
+        self.lp = Integrator::select(active, &self.lp, &prev.lp);
+    }
+
+    /// Same as [`Self::process`], but also pushes `g` and `res` into `recorder`.
+    ///
+    /// Recording is opt-in: `process` itself is unaffected.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn process_recorded(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+        recorder: &mut CoeffRecorder<N>,
+    ) {
+        recorder.push(SvfCoeffs::new(g, res));
+        self.process(x, g, res);
+    }
+
+    /// Same as [`Self::process`], but also returns a mask of the lanes where `g` and `res`
+    /// were outside of the stable range this sample (see [`stable_lanes`]).
+    ///
+    /// Meant for catching coefficients momentarily going out of bounds
+    /// in heavily modulated settings
+    #[inline]
+    pub fn process_monitored(
+        &mut self,
+        x: VFloat<N>,
+        g: VFloat<N>,
+        res: VFloat<N>,
+    ) -> Mask<i32, N> {
+        self.process(x, g, res);
+        !stable_lanes(g, res)
+    }
+
+    /// Process one sample of `N` voices stored in structure-of-arrays form.
+    ///
+    /// Each voice's input sample, cutoff frequency (in Hz) and resonance are gathered, from
+    /// `inputs`, `cutoffs` and `resos` respectively, into their own lane. After processing,
+    /// the output selected by `shape` is scattered back into `outputs`, one voice per element.
+    ///
+    /// # Panics
+    ///
+    /// If any of the slices' lengths isn't `N`
+    #[inline]
+    pub fn process_soa(
+        &mut self,
+        inputs: &[f32],
+        cutoffs: &[f32],
+        resos: &[f32],
+        outputs: &mut [f32],
+        sample_rate: f32,
+        shape: FilterShape<VFloat<N>>,
+    ) {
+        for len in [inputs.len(), cutoffs.len(), resos.len(), outputs.len()] {
+            assert_eq!(len, N, "SoA slices must hold exactly one value per lane");
+        }
+
+        let g = g_from_hz(Simd::from_slice(cutoffs), sample_rate);
+        self.process(Simd::from_slice(inputs), g, Simd::from_slice(resos));
+        Filter::get(self, shape).copy_to_slice(outputs);
+    }
+
+    /// Filter `buf` in place, replacing each sample with the output selected by `shape`.
+    ///
+    /// `buf` is read as consecutive frames of `N` samples, one per lane (i.e. interleaved
+    /// audio with `N` channels, or, with `N = 1`, a single channel, as delivered by most
+    /// hosts). Each frame goes to the same lanes, so a stream can be split into buffers
+    /// of any number of whole frames, and filtered one buffer after the other.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `buf` isn't a multiple of `N`
+    #[inline]
+    pub fn filter_slice(
+        &mut self,
+        buf: &mut [f32],
+        g: VFloat<N>,
+        res: VFloat<N>,
+        shape: FilterShape<VFloat<N>>,
+    ) {
+        assert!(
+            buf.len().is_multiple_of(N),
+            "the buffer must hold whole frames of N samples"
+        );
+
+        for frame in buf.chunks_exact_mut(N) {
+            self.process(Simd::from_slice(frame), g, res);
+            Filter::get(self, shape).copy_to_slice(frame);
+        }
+    }
+
+    /// Update the filter's internal state, with the cutoff frequency exponentially
+    /// modulated by `fm_signal`, sample-accurately (filter FM).
+    ///
+    /// The integrator pre-gain becomes `base_g * 2^(fm_depth * fm_signal)`, i.e. `fm_depth` is
+    /// the modulation depth in octaves. Instead of recomputing the `tan` prewarping for every
+    /// sample, the modulation is applied directly to `g`, which is proportional to the cutoff
+    /// well below nyquist, but compresses intervals close to it (the same warping as the bilinear
+    /// transform). Any non-negative `g` corresponds to a valid cutoff, the result is only
+    /// clamped to the range `g_from_hz` produces. See [`Self::process`] for the other parameters.
+    ///
+    /// Audio-rate modulation produces sidebands that extend past the input's bandwidth, and
+    /// that will fold back below nyquist (alias) when they cross it. Expect this to be audible
+    /// with high modulation depths and high cutoffs, unless the filter is oversampled.
+    #[inline]
+    pub fn process_fm(
+        &mut self,
+        x: VFloat<N>,
+        base_g: VFloat<N>,
+        fm_signal: VFloat<N>,
+        fm_depth: VFloat<N>,
+        res: VFloat<N>,
+    ) {
+        let g = base_g * (fm_depth * fm_signal).exp2();
+        self.process(x, g.simd_clamp(Simd::splat(0.), Simd::splat(MAX_G)), res);
+    }
+
+    /// Update the filter's internal state by running two steps of the filter at twice the
+    /// sample rate, reducing the warping (cramping) of its response at high cutoff
+    /// frequencies, without oversampling the rest of the signal chain.
+    ///
+    /// `x`, `g` and `res` are the same as in [`Self::process`], at the outer sample rate.
+    /// The value of `g` for the inner sample rate, `tan(w_c/4)`, is derived from it with the
+    /// half-angle formula `g / (1 + sqrt(1 + g^2))`, i.e. without recomputing `tan`.
+    /// The first step is fed the average of the previous and current inputs, the second
+    /// one, the current input. The outputs are those of the second step.
+    ///
+    /// This costs a little more than twice a regular call to `process`. In exchange, the
+    /// response between the cutoff and nyquist is much closer to the analog prototype's.
+    /// For instance, at a `48 kHz` sample rate, with `res = 0.3` and a cutoff at `12 kHz`,
+    /// the analog lowpass response is at `-4.3 dB` at `19.2 kHz`, where the regular filter
+    /// is at `-18.6 dB`, and this one at `-9.4 dB`. However, the input interpolation
+    /// causes some droop in the top octave, lowering the resonant peak for high cutoffs
+    /// (by about `1.3 dB` at `12 kHz` and `3.1 dB` at `18 kHz` in the same setting).
+    #[inline]
+    pub fn process_2x_internal(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) {
+        let one = Simd::splat(1.);
+        let g_half = g / (one + g.mul_add(g, one).sqrt());
+
+        let mid = (self.x + x) * Simd::splat(0.5);
+        self.process(mid, g_half, res);
+        self.process(x, g_half, res);
+    }
+
+    /// Get all of the standard outputs at once, each computed only once
+    #[inline]
+    pub fn outputs(&self) -> SvfOutputs<N> {
+        SvfOutputs {
+            passthrough: *self.get_passthrough(),
+            lowpass: *self.get_lowpass(),
+            bandpass: *self.get_bandpass(),
+            unit_bandpass: *self.get_unit_bandpass(),
+            highpass: *self.get_highpass(),
+            allpass: self.get_allpass(),
+            notch: self.get_notch(),
+        }
+    }
+
+    /// Get the lowpass output, along with its time derivative, in units per second.
+    /// `g` must be the value passed to `process`.
+    ///
+    /// In the analog prototype, the lowpass is the integral of the bandpass, scaled by the
+    /// cutoff frequency `w_a`, in radians per second: `d(lp)/dt = w_a * bp`. The digital
+    /// filter is the bilinear transform of the prototype with the prewarped cutoff
+    /// `w_a = 2 * sample_rate * g`, hence, the derivative is `2 * sample_rate * g * bp`.
+    /// This is the derivative the trapezoidal integrator uses at the current sample, rather
+    /// than a finite difference of the output, so it doesn't add any delay. For signals well
+    /// below nyquist, it closely matches the true derivative.
+    #[inline]
+    pub fn get_lowpass_and_derivative(
+        &self,
+        g: VFloat<N>,
+        sample_rate: f32,
+    ) -> (VFloat<N>, VFloat<N>) {
+        let w_a = g * Simd::splat(2. * sample_rate);
+        (*self.get_lowpass(), w_a * self.get_bandpass())
+    }
+
+    /// Get the output selected by `shape`, soft-limited such that its magnitude always
+    /// stays below `ceiling` (which must be positive), e.g. to keep a self-oscillating filter
+    /// from blowing up the speakers.
+    ///
+    /// This uses the same limiter as [`Self::process_limited`]: outputs up to half the
+    /// ceiling are left untouched, and only the excess over that knee is compressed, with
+    /// [`nonlin::saturate`], so that louder outputs approach the ceiling, but never exceed
+    /// it. Only the output is limited, not the filter's state.
+    #[inline]
+    pub fn get_limited(&self, shape: FilterShape<VFloat<N>>, ceiling: VFloat<N>) -> VFloat<N> {
+        soft_limit(self.get(shape), ceiling)
+    }
+}
+
+/// All of the standard outputs of an [`SVF`], computed by the last `process` call,
+/// see [`SVF::outputs`], e.g. to blend between them
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SvfOutputs<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    pub passthrough: VFloat<N>,
+    pub lowpass: VFloat<N>,
+    pub bandpass: VFloat<N>,
+    pub unit_bandpass: VFloat<N>,
+    pub highpass: VFloat<N>,
+    pub allpass: VFloat<N>,
+    pub notch: VFloat<N>,
+}
+
+/// Resonant highpass filter with the same response as [`SVF::get_highpass`], but
+/// guaranteed to have exactly zero gain at DC, even in the presence of rounding errors.
+///
+/// A regular `SVF` fed a constant input can settle with its integrator states a few ULPs
+/// away from their ideal values, leaking a tiny amount of DC through the highpass output.
+/// Here, the SVF is instead fed the first difference of the input, `x[n] - x[n-1]`, which is
+/// exactly `0` for constant inputs, so the filter simply decays to silence. The transfer
+/// function `(1 - z^-1)^2 / A(z)` of the highpass is then recovered from the differenced
+/// signal's highpass and bandpass outputs: `hp + bp / g = 2 (1 - z^-1) / A(z)`.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct DcBlockedHighpass<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    svf: SVF<N>,
+    x1: VFloat<N>,
+    hp: VFloat<N>,
+}
+
+impl<const N: usize> DcBlockedHighpass<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.svf.reset();
+        self.x1 = Simd::splat(0.);
+        self.hp = Simd::splat(0.);
+    }
+
+    /// Update the filter's internal state, see [`SVF::process`].
+    ///
+    /// `g` must be strictly positive.
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, g: VFloat<N>, res: VFloat<N>) {
+        self.svf.process(x - self.x1, g, res);
+        self.x1 = x;
+        let &bp = self.svf.get_bandpass();
+        let &hp = self.svf.get_highpass();
+        self.hp = (hp + bp / g) * Simd::splat(0.5);
+    }
+
+    #[inline]
+    pub fn get_highpass(&self) -> &VFloat<N> {
+        &self.hp
+    }
+}
+
+/// Coefficients of the [`SVF`] filter, see [`SVF::process`]
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct SvfCoeffs<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    pub g: VFloat<N>,
+    pub res: VFloat<N>,
+}
+
+impl<const N: usize> SvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(g: VFloat<N>, res: VFloat<N>) -> Self {
+        Self { g, res }
+    }
+
+    /// Same as [`Self::new`], but fails, instead of producing coefficients for which the
+    /// filter may be unstable (see [`stable_lanes`]). If both `g` and `res` are out of range,
+    /// the error is reported for `g`.
+    #[inline]
+    pub fn try_new(g: VFloat<N>, res: VFloat<N>) -> Result<Self, CoeffError> {
+        let zero = Simd::splat(0.);
+
+        let bad_g = !(g.is_finite() & g.simd_ge(zero));
+        if bad_g.any() {
+            return Err(CoeffError::G {
+                lanes: bad_g.to_bitmask(),
+            });
+        }
+
+        let bad_res = !(res.is_finite() & res.simd_ge(zero));
+        if bad_res.any() {
+            return Err(CoeffError::Res {
+                lanes: bad_res.to_bitmask(),
+            });
+        }
+
+        Ok(Self::new(g, res))
+    }
+
+    /// Group delay, in samples, of the filter's poles at DC: `res / (2 * g)`.
+    ///
+    /// This is also the group delay at DC of the lowpass, bandpass,
+    /// highpass and notch outputs (the allpass' is twice as long).
+    #[inline]
+    pub fn latency(&self) -> VFloat<N> {
+        self.res / (self.g + self.g)
+    }
+}
+
+impl<const N: usize> smooth::LinearCoeffs for SvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn increment(from: &Self, to: &Self, steps: usize) -> Self {
+        Self::new(
+            smooth::LinearCoeffs::increment(&from.g, &to.g, steps),
+            smooth::LinearCoeffs::increment(&from.res, &to.res, steps),
+        )
+    }
+
+    #[inline]
+    fn advance(&mut self, inc: &Self) {
+        self.g += inc.g;
+        self.res += inc.res;
+    }
+}
+
+impl<const N: usize> TryFrom<(VFloat<N>, VFloat<N>)> for SvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Error = CoeffError;
+
+    /// See [`SvfCoeffs::try_new`]
+    #[inline]
+    fn try_from((g, res): (VFloat<N>, VFloat<N>)) -> Result<Self, Self::Error> {
+        Self::try_new(g, res)
+    }
+}
+
+/// Interpolate between the coefficients `a` (`t = 0`) and `b` (`t = 1`), in each lane, for
+/// smooth morphing between two filter settings. `t` is clamped to `[0 ; 1]`.
+///
+/// The cutoff frequency is interpolated geometrically (linearly in octaves), as `w_c = 2 *
+/// atan(g)`, rather than `g` itself, whose warping would make the glide rush through the
+/// highs, and `res` is interpolated geometrically too, i.e. linearly in `log(q)`, so that a
+/// linear sweep of `t` sounds even. If either end is `0` (DC, or self-oscillation), the
+/// corresponding value falls back to linear interpolation.
+#[inline]
+pub fn lerp_coeffs<const N: usize>(a: SvfCoeffs<N>, b: SvfCoeffs<N>, t: VFloat<N>) -> SvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn geometric(x0: f32, x1: f32, t: f32) -> f32 {
+        if x0 > 0. && x1 > 0. {
+            x0 * (x1 / x0).powf(t)
+        } else {
+            (x1 - x0).mul_add(t, x0)
+        }
+    }
+
+    let t = t.simd_clamp(Simd::splat(0.), Simd::splat(1.));
+    let max_w = 0.999 * PI;
+
+    let g = Simd::from_array(core::array::from_fn(|i| {
+        let w = geometric(2. * a.g[i].atan(), 2. * b.g[i].atan(), t[i]);
+        (0.5 * w.clamp(0., max_w)).tan()
+    }));
+    let res = Simd::from_array(core::array::from_fn(|i| {
+        geometric(a.res[i], b.res[i], t[i])
+    }));
+
+    SvfCoeffs::new(g, res)
+}
+
+/// [`SvfCoeffs`], along with the values [`SVF::process`] derives from them every sample,
+/// computed once, see [`SVF::process_with`]. For filters whose coefficients rarely change,
+/// e.g. the bands of a static EQ.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct PrecomputedSvfCoeffs<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    g: VFloat<N>,
+    res: VFloat<N>,
+    // res + g
+    g1: VFloat<N>,
+    // 1 / (1 + res * g + g^2)
+    d: VFloat<N>,
+}
+
+impl<const N: usize> PrecomputedSvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(g: VFloat<N>, res: VFloat<N>) -> Self {
+        let g1 = res + g;
+        Self {
+            g,
+            res,
+            g1,
+            d: g1.mul_add(g, Simd::splat(1.)).recip(),
+        }
+    }
+
+    #[inline]
+    pub fn g(&self) -> &VFloat<N> {
+        &self.g
+    }
+
+    #[inline]
+    pub fn res(&self) -> &VFloat<N> {
+        &self.res
+    }
+}
+
+impl<const N: usize> From<SvfCoeffs<N>> for PrecomputedSvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn from(coeffs: SvfCoeffs<N>) -> Self {
+        Self::new(coeffs.g, coeffs.res)
+    }
+}
+
+/// Compute the coefficients of a cascade of SVF sections, realizing a high-order analog
+/// prototype, with the cutoff frequency `cutoff_hz`.
+///
+/// Each section is given as a pole pair of the prototype (normalized to a cutoff of `1`):
+/// `(freq_ratio, res)`, with a denominator of `(s / freq_ratio)^2 + res * (s / freq_ratio) + 1`.
+///
+/// The bilinear transform warps frequencies, so prewarping each section independently, at
+/// `freq_ratio * cutoff_hz`, would match each section's own corner, but not the response of
+/// the whole cascade, whose corners would drift apart near nyquist. Instead, all sections
+/// share a single prewarp reference, `g = tan(w_c/2)`, and are scaled from it
+/// (`freq_ratio * g`) as in the analog domain, so that the cascade's response at
+/// `cutoff_hz` is exactly that of the prototype at `1` (e.g. `-3.01 dB` for a Butterworth).
+/// The prototype's shape is thus preserved around the cutoff, in exchange for the other
+/// sections' corners being slightly off.
+#[inline]
+pub fn cascade_coeffs<const N: usize, const S: usize>(
+    cutoff_hz: VFloat<N>,
+    sample_rate: f32,
+    sections: &[(f32, f32); S],
+) -> [SvfCoeffs<N>; S]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let g = g_from_hz(cutoff_hz, sample_rate);
+    sections.map(|(freq_ratio, res)| {
+        SvfCoeffs::new(
+            (g * Simd::splat(freq_ratio)).simd_min(Simd::splat(MAX_G)),
+            Simd::splat(res),
+        )
+    })
+}
+
+/// The sections (see [`cascade_coeffs`]) of a Butterworth filter of order `2 * S`: all at
+/// the cutoff frequency, with `res = 2 * sin((2k + 1) * pi / (4 * S))`, for `k` in `0..S`.
+#[inline]
+pub fn butterworth_sections<const S: usize>() -> [(f32, f32); S] {
+    core::array::from_fn(|k| {
+        let angle = (2 * k + 1) as f32 * PI / (4 * S) as f32;
+        (1., 2. * angle.sin())
+    })
+}
+
+/// Generates `g` in `[0 ; MAX_G]` (the range of [`g_from_hz`]) and `res` in `[0 ; 4]`,
+/// i.e. from self-oscillation to heavily overdamped, all stable
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for SvfCoeffs<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(
+            arbitrary_vfloat(u, 0.0..=MAX_G)?,
+            arbitrary_vfloat(u, 0.0..=4.)?,
+        ))
+    }
+}
+
+/// Generates bounded integrator states, see [`Integrator`]'s impl,
+/// with the cached outputs cleared, as with [`SVF::flush_outputs`]
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for SVF<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut svf = Self {
+            bp: arbitrary::Arbitrary::arbitrary(u)?,
+            lp: arbitrary::Arbitrary::arbitrary(u)?,
+            ..Default::default()
+        };
+        svf.flush_outputs();
+        Ok(svf)
+    }
+}
+
+/// Serialized as a tuple of the input, the cached highpass and (scaled) bandpass outputs,
+/// and the states and outputs of both integrators, each a tuple of the `N` lanes. Restoring
+/// it and resuming processing produces exactly the same output as never having serialized.
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for SVF<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_vfloats(
+            [
+                self.x,
+                self.hp,
+                self.bp1,
+                self.bp.s,
+                self.bp.out,
+                self.lp.s,
+                self.lp.out,
+            ],
+            serializer,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for SVF<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, hp, bp1, bp_s, bp_out, lp_s, lp_out] = deserialize_vfloats(deserializer)?;
+        Ok(Self {
+            x,
+            hp,
+            bp: Integrator {
+                s: bp_s,
+                out: bp_out,
+            },
+            bp1,
+            lp: Integrator {
+                s: lp_s,
+                out: lp_out,
+            },
+        })
+    }
+}
+
+/// Fixed-capacity recording of the coefficients an [`SVF`] was processed with, one entry per
+/// sample, filled by [`SVF::process_recorded`], e.g. to capture modulation for automation.
+///
+/// Once full, the oldest entries are overwritten.
+#[cfg(feature = "std")]
+#[derive(Default, Clone)]
+pub struct CoeffRecorder<const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    buf: VecDeque<SvfCoeffs<N>>,
+    capacity: usize,
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> CoeffRecorder<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create an empty recorder, holding at most `capacity` entries. Memory for
+    /// all of them is allocated upfront, so that recording never allocates.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buf: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, coeffs: SvfCoeffs<N>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.buf.len() == self.capacity {
+            self.buf.pop_front();
+        }
+        self.buf.push_back(coeffs);
+    }
+
+    /// Remove and return all the recorded entries, from oldest to newest
+    #[inline]
+    pub fn drain(&mut self) -> impl Iterator<Item = SvfCoeffs<N>> + '_ {
+        self.buf.drain(..)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+}
+
+/// The different outputs of the [`SVF`] filter.
+///
+/// The gain carried by the shelving variants follows the same
+/// convention as the corresponding `SVF::get_*_shelf` method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterShape<G> {
+    Passthrough,
+    Lowpass,
+    Bandpass,
+    UnitBandpass,
+    Highpass,
+    Allpass,
+    Notch,
+    LowShelf(G),
+    BandShelf(G),
+    HighShelf(G),
+}
+
+/// `20 * log10(2)`, the asymptotic slope, in dB per octave, contributed
+/// by each order of a filter's rolloff (commonly rounded to `6 dB/oct`)
+pub(crate) const DB_PER_OCTAVE_PER_ORDER: f64 = 6.020_599_913_279_624;
+
+impl<G> FilterShape<G> {
+    /// Convert the gain carried by the shelving variants with `f`
+    #[inline]
+    pub fn map<H>(self, f: impl FnOnce(G) -> H) -> FilterShape<H> {
+        match self {
+            Self::Passthrough => FilterShape::Passthrough,
+            Self::Lowpass => FilterShape::Lowpass,
+            Self::Bandpass => FilterShape::Bandpass,
+            Self::UnitBandpass => FilterShape::UnitBandpass,
+            Self::Highpass => FilterShape::Highpass,
+            Self::Allpass => FilterShape::Allpass,
+            Self::Notch => FilterShape::Notch,
+            Self::LowShelf(gain) => FilterShape::LowShelf(f(gain)),
+            Self::BandShelf(gain) => FilterShape::BandShelf(f(gain)),
+            Self::HighShelf(gain) => FilterShape::HighShelf(f(gain)),
+        }
+    }
+
+    /// Theoretical asymptotic slope, in dB per octave, of this shape's rolloff, away from its
+    /// passband: `-12 dB/oct` (precisely `-12.04`) for the lowpass and highpass, and `-6 dB/oct`
+    /// on both sides of the bandpasses. Shapes that don't roll off (passthrough, allpass,
+    /// notch, shelves) return `0.0`.
+    ///
+    /// This is the slope of the analog prototype, the digital filters match it well below
+    /// nyquist. Near nyquist, the lowpass' rolloff is steeper, due to its zero at nyquist.
+    #[inline]
+    pub fn slope_db_per_octave(&self) -> f64 {
+        match self {
+            Self::Lowpass | Self::Highpass => -2. * DB_PER_OCTAVE_PER_ORDER,
+            Self::Bandpass | Self::UnitBandpass => -DB_PER_OCTAVE_PER_ORDER,
+            Self::Passthrough
+            | Self::Allpass
+            | Self::Notch
+            | Self::LowShelf(_)
+            | Self::BandShelf(_)
+            | Self::HighShelf(_) => 0.,
+        }
+    }
+}
+
+/// Frequency response, as `(real, imaginary)` parts, of the output selected by `shape`, at the
+/// normalized frequency `omega` (in radians per sample), in each lane. Vectorized counterpart
+/// of [`trnasfer::z_response`], computed without complex number types.
+///
+/// The integrators' transfer function `g * (z + 1) / (z - 1)` is, on the unit circle, purely
+/// imaginary: `-j * g / tan(omega / 2)`, so the responses of the three taps only call for
+/// real arithmetic. With `w = tan(omega / 2) / g`, the lowpass is `1 / (1 - w^2 + j * res * w)`,
+/// the bandpass `j * w` times that, and the highpass `j * w` times the bandpass.
+#[inline]
+fn response_at<const N: usize>(
+    shape: FilterShape<VFloat<N>>,
+    g: VFloat<N>,
+    res: VFloat<N>,
+    omega: VFloat<N>,
+) -> (VFloat<N>, VFloat<N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let zero = Simd::splat(0.);
+    let one = Simd::splat(1.);
+
+    let w = Simd::from_array((omega * Simd::splat(0.5)).to_array().map(f32::tan)) / g;
+
+    // multiplication by j * w
+    let jw = |(re, im): (VFloat<N>, VFloat<N>)| (-im * w, re * w);
+
+    let re_d = w.mul_add(-w, one);
+    let im_d = res * w;
+    let inv_norm = re_d.mul_add(re_d, im_d * im_d).recip();
+
+    let lp = (re_d * inv_norm, -im_d * inv_norm);
+    let bp = jw(lp);
+    let hp = jw(bp);
+    let bp1 = (bp.0 * res, bp.1 * res);
+
+    match shape {
+        FilterShape::Passthrough => (one, zero),
+        FilterShape::Lowpass => lp,
+        FilterShape::Bandpass => bp,
+        FilterShape::UnitBandpass => bp1,
+        FilterShape::Highpass => hp,
+        FilterShape::Allpass => (
+            bp1.0.mul_add(Simd::splat(2.), -one),
+            bp1.1 * Simd::splat(2.),
+        ),
+        FilterShape::Notch => (one - bp1.0, -bp1.1),
+        FilterShape::LowShelf(m) => (
+            lp.0.mul_add(m, bp1.0).mul_add(m, hp.0),
+            lp.1.mul_add(m, bp1.1).mul_add(m, hp.1),
+        ),
+        FilterShape::BandShelf(m) => (bp1.0.mul_add(m - one, one), bp1.1 * (m - one)),
+        FilterShape::HighShelf(m) => (
+            hp.0.mul_add(m, bp1.0).mul_add(m, lp.0),
+            hp.1.mul_add(m, bp1.1).mul_add(m, lp.1),
+        ),
+    }
+}
+
+/// Magnitude (linear) of the frequency response of the output selected by `shape`, at the
+/// normalized frequency `omega` (in radians per sample, in `[0 ; pi[`), given the `g` and
+/// `res` coefficients passed to [`SVF::process`], in each lane.
+///
+/// This only uses real arithmetic on SIMD vectors, so it doesn't require the `num` feature,
+/// e.g. for drawing a response curve, or metering, at runtime. With `num`, see
+/// [`trnasfer::z_response`] for the complex response.
+#[inline]
+pub fn magnitude_at<const N: usize>(
+    shape: FilterShape<VFloat<N>>,
+    g: VFloat<N>,
+    res: VFloat<N>,
+    omega: VFloat<N>,
+) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (re, im) = response_at(shape, g, res, omega);
+    re.mul_add(re, im * im).sqrt()
+}
+
+/// Steady-state amplitude and phase (in radians, in `]-pi ; pi]`) of the output selected by
+/// `shape` (including, for the shelving shapes, their gain), when the input is a sinusoid of
+/// amplitude `input_amp` and frequency `freq_hz` (below `sample_rate / 2`), given the `g` and
+/// `res` coefficients passed to [`SVF::process`].
+///
+/// This is computed directly from the filter's transfer function, without running it, and
+/// each lane can hold a different frequency, or a different set of coefficients, e.g. to
+/// evaluate many points of a response curve at once. No `num` feature required.
+#[inline]
+pub fn steady_state_output<const N: usize>(
+    shape: FilterShape<VFloat<N>>,
+    g: VFloat<N>,
+    res: VFloat<N>,
+    freq_hz: VFloat<N>,
+    sample_rate: f32,
+    input_amp: VFloat<N>,
+) -> (VFloat<N>, VFloat<N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let omega = freq_hz * Simd::splat(core::f32::consts::TAU / sample_rate);
+    let (re, im) = response_at(shape, g, res, omega);
+
+    let amp = re.mul_add(re, im * im).sqrt() * input_amp;
+    let phase = Simd::from_array(core::array::from_fn(|i| im[i].atan2(re[i])));
+
+    (amp, phase)
+}
+
+/// Scalar convenience methods, for code processing a single (mono) signal, one
+/// sample at a time, that doesn't want to deal with vectors at all
+impl SVF<1> {
+    /// Same as [`Self::process`], with a single lane
+    #[inline]
+    pub fn process_mono(&mut self, x: f32, g: f32, res: f32) {
+        self.process(Simd::splat(x), Simd::splat(g), Simd::splat(res));
+    }
+
+    /// Get the output selected by `shape`, see [`Filter::get`]
+    #[inline]
+    pub fn get_mono(&self, shape: FilterShape<f32>) -> f32 {
+        self.get(shape.map(Simd::splat))[0]
+    }
+}
+
+impl<const N: usize> Filter<N> for SVF<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Coeffs = SvfCoeffs<N>;
+    type Shape = FilterShape<VFloat<N>>;
+
+    #[inline]
+    fn process(&mut self, x: VFloat<N>, coeffs: &Self::Coeffs) {
+        self.process(x, coeffs.g, coeffs.res);
+    }
+
+    #[inline]
+    fn get(&self, shape: Self::Shape) -> VFloat<N> {
+        match shape {
+            FilterShape::Passthrough => *self.get_passthrough(),
+            FilterShape::Lowpass => *self.get_lowpass(),
+            FilterShape::Bandpass => *self.get_bandpass(),
+            FilterShape::UnitBandpass => *self.get_unit_bandpass(),
+            FilterShape::Highpass => *self.get_highpass(),
+            FilterShape::Allpass => self.get_allpass(),
+            FilterShape::Notch => self.get_notch(),
+            FilterShape::LowShelf(gain) => self.get_low_shelf(gain),
+            FilterShape::BandShelf(gain) => self.get_band_shelf(gain),
+            FilterShape::HighShelf(gain) => self.get_high_shelf(gain),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    #[inline]
+    fn latency(&self, coeffs: &Self::Coeffs) -> VFloat<N> {
+        coeffs.latency()
+    }
+}
+
+/// [`SVF`] whose lane count is chosen at runtime, depending on the SIMD instruction sets
+/// that the CPU supports: 8 lanes if AVX is available, 4 otherwise.
+///
+/// This allows shipping a single binary that makes the best use of the available SIMD
+/// width. The processing interface works on scalar slices, one element per lane
+/// (i.e. per voice), see [`Self::lanes`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+pub struct DynSvf(DynSvfInner);
+
+/// Kept private, so that [`DynSvf::new`], which checks for AVX support,
+/// is the only way to create the 8-lane variant
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+enum DynSvfInner {
+    X4(SVF<4>),
+    X8(SVF<8>),
+}
+
+#[cfg(feature = "std")]
+impl Default for DynSvf {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl DynSvf {
+    /// Create a filter with the widest lane count supported by the current CPU
+    #[inline]
+    pub fn new() -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        if std::is_x86_feature_detected!("avx") {
+            return Self(DynSvfInner::X8(SVF::default()));
+        }
+
+        Self(DynSvfInner::X4(SVF::default()))
+    }
+
+    /// The number of lanes (voices) of this filter, i.e. the length of the slices
+    /// passed to [`Self::process`]
+    #[inline]
+    pub fn lanes(&self) -> usize {
+        match self.0 {
+            DynSvfInner::X4(_) => 4,
+            DynSvfInner::X8(_) => 8,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        match &mut self.0 {
+            DynSvfInner::X4(svf) => svf.reset(),
+            DynSvfInner::X8(svf) => svf.reset(),
+        }
+    }
+
+    /// Process one sample of each voice, with the inputs, `g`s and `res`es given in `x`, `g`
+    /// and `res` respectively (see [`SVF::process`]), and write the output selected by
+    /// `shape` (whose gain, if any, applies to all voices) to `out`.
+    ///
+    /// # Panics
+    ///
+    /// If any of the slices' lengths isn't [`Self::lanes`]
+    #[inline]
+    pub fn process(
+        &mut self,
+        x: &[f32],
+        g: &[f32],
+        res: &[f32],
+        shape: FilterShape<f32>,
+        out: &mut [f32],
+    ) {
+        match &mut self.0 {
+            DynSvfInner::X4(svf) => process_slices(svf, x, g, res, shape, out),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            // SAFETY: `DynSvfInner` is private, and `DynSvf::new` only
+            // creates this variant after detecting AVX support
+            DynSvfInner::X8(svf) => unsafe { process_slices_avx(svf, x, g, res, shape, out) },
+            #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+            DynSvfInner::X8(svf) => process_slices(svf, x, g, res, shape, out),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn process_slices<const N: usize>(
+    svf: &mut SVF<N>,
+    x: &[f32],
+    g: &[f32],
+    res: &[f32],
+    shape: FilterShape<f32>,
+    out: &mut [f32],
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    for len in [x.len(), g.len(), res.len(), out.len()] {
+        assert_eq!(len, N, "slices must hold exactly one value per lane");
+    }
+
+    svf.process(
+        Simd::from_slice(x),
+        Simd::from_slice(g),
+        Simd::from_slice(res),
+    );
+    Filter::get(svf, shape.map(Simd::splat)).copy_to_slice(out);
+}
+
+/// Same as [`process_slices`], but compiled with AVX enabled, so
+/// that 8-lane vectors fit in a single register
+///
+/// # Safety
+///
+/// The CPU must support AVX
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+#[target_feature(enable = "avx")]
+unsafe fn process_slices_avx(
+    svf: &mut SVF<8>,
+    x: &[f32],
+    g: &[f32],
+    res: &[f32],
+    shape: FilterShape<f32>,
+    out: &mut [f32],
+) {
+    process_slices(svf, x, g, res, shape, out)
+}
+
+/// Generate the source code of a self-contained C function implementing the SVF's update
+/// equations, with the coefficients `g` and `res` (which must be finite) baked in, and
+/// returning the output selected by `shape`, along with the struct holding its state.
+///
+/// ```c
+/// svf_state state = {0};
+/// float y = svf_process(&state, x);
+/// ```
+#[cfg(feature = "codegen")]
+pub fn to_c_snippet(shape: FilterShape<f32>, g: f32, res: f32) -> String {
+    // `{:?}` formats floats with enough digits to round-trip
+    let lit = |v: f32| format!("{v:?}f");
+
+    let output = match shape {
+        FilterShape::Passthrough => "x".into(),
+        FilterShape::Lowpass => "lp".into(),
+        FilterShape::Bandpass => "bp".into(),
+        FilterShape::UnitBandpass => "bp1".into(),
+        FilterShape::Highpass => "hp".into(),
+        FilterShape::Allpass => "2.0f * bp1 - x".into(),
+        FilterShape::Notch => "x - bp1".into(),
+        FilterShape::LowShelf(m) => format!("{m} * ({m} * lp + bp1) + hp", m = lit(m)),
+        FilterShape::BandShelf(m) => format!("{m} * bp1 + x - bp1", m = lit(m)),
+        FilterShape::HighShelf(m) => format!("{m} * ({m} * hp + bp1) + lp", m = lit(m)),
+    };
+
+    let g1 = res + g;
+
+    format!(
+        "typedef struct {{
+    float s1; /* bandpass integrator state */
+    float s2; /* lowpass integrator state */
+}} svf_state;
+
+/* TPT state variable filter, g = {g}, res = {res} */
+static inline float svf_process(svf_state *state, float x) {{
+    const float g = {g};
+    const float res = {res};
+    const float g1 = {g1};
+    const float d = {d};
+
+    float hp = (x - g1 * state->s1 - state->s2) * d;
+
+    float v1 = g * hp;
+    float bp = v1 + state->s1;
+    state->s1 = bp + v1;
+
+    float v2 = g * bp;
+    float lp = v2 + state->s2;
+    state->s2 = lp + v2;
+
+    float bp1 = res * bp;
+    (void)bp1;
+
+    return {output};
+}}
+",
+        g = lit(g),
+        res = lit(res),
+        g1 = lit(g1),
+        d = lit(g1.mul_add(g, 1.).recip()),
+    )
+}
+
+/// Fit an SVF to a target impulse response, `target[0]` being the response to the impulse
+/// itself, returning the coefficients and output shape (among the ones without a gain,
+/// except [`FilterShape::Passthrough`]) whose impulse response minimizes the sum of the
+/// squared differences with `target`, over its length. The coefficients are the same in
+/// every lane.
+///
+/// For each shape, the cutoff frequency and Q are first searched for on a coarse grid
+/// (frequencies from `10 Hz` to just below nyquist, and Qs from `0.1` to `20`, clamped to
+/// these ranges throughout), which is then refined with a pattern search, as in
+/// [`eq::fit_bell`]. The fit is only as good as `target` is long: it must cover most of
+/// the response's decay, and low cutoffs or high resonance make for long responses.
+///
+/// # Panics
+///
+/// If `target` is empty
+#[cfg(feature = "std")]
+pub fn fit_to_impulse<const N: usize>(
+    target: &[f32],
+    sample_rate: f32,
+) -> (SvfCoeffs<N>, FilterShape<VFloat<N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    assert!(
+        !target.is_empty(),
+        "the target impulse response can't be empty"
+    );
+
+    // parameters: [log2(freq_hz), log2(q)]
+    let ranges = [
+        (10f64.log2(), (0.499 * f64::from(sample_rate)).log2(), 48),
+        (0.1f64.log2(), 20f64.log2(), 16),
+    ];
+
+    let coeffs = |[f, q]: [f64; 2]| {
+        SvfCoeffs::<1>::new(
+            g_from_hz::<1>(Simd::splat(f.exp2() as f32), sample_rate),
+            Simd::splat(q.exp2().recip() as f32),
+        )
+    };
+
+    let cost = |shape: FilterShape<Simd<f32, 1>>, p: [f64; 2]| -> f64 {
+        SVF::<1>::default()
+            .to_fir(&coeffs(p), shape, target.len())
+            .iter()
+            .zip(target)
+            .map(|(h, t)| f64::from((h - t) * (h - t)))
+            .sum()
+    };
+
+    let shapes = [
+        FilterShape::Lowpass,
+        FilterShape::Bandpass,
+        FilterShape::UnitBandpass,
+        FilterShape::Highpass,
+        FilterShape::Allpass,
+        FilterShape::Notch,
+    ];
+
+    let mut best_shape = FilterShape::Lowpass;
+    let mut best = [0.; 2];
+    let mut best_cost = f64::INFINITY;
+
+    for shape in shapes {
+        let (shape_best, shape_cost) = grid_pattern_search(ranges, |p| cost(shape, p));
+        if shape_cost < best_cost {
+            (best_shape, best, best_cost) = (shape, shape_best, shape_cost);
+        }
+    }
+
+    let SvfCoeffs { g, res } = coeffs(best);
+    (
+        SvfCoeffs::new(Simd::splat(g[0]), Simd::splat(res[0])),
+        best_shape.map(|gain| Simd::splat(gain[0])),
+    )
+}
+
+#[cfg(feature = "num")]
+pub mod trnasfer {
+
+    use super::*;
+
+    #[inline]
+    fn two<T: Float>(res: T) -> T {
+        res + res
+    }
+
+    #[inline]
+    fn h_denominator<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        s * (s + two(res)) + T::one()
+    }
+
+    /// Frequency, in Hz, and gain, in dB, of the resonant peak of the digital SVF's lowpass
+    /// output, given the `g` and `res` coefficients passed to [`SVF::process`].
+    ///
+    /// Note that `res` here is _not_ the prototype resonance of the other functions
+    /// in this module, which is half of it.
+    ///
+    /// The lowpass only has a resonant peak when `res < sqrt(2)`; otherwise, its magnitude is
+    /// maximal at DC, and `(0, 0)` is returned. The bandpass, on the other hand, always peaks
+    /// exactly at the cutoff frequency, with a gain of `1 / res`.
+    #[inline]
+    pub fn resonant_peak<T: Float>(g: T, res: T, sample_rate: T) -> (T, T) {
+        let r = res / two(T::one());
+        let w_a2 = T::one() - two(r * r);
+
+        if w_a2 <= T::zero() {
+            return (T::zero(), T::zero());
+        }
+
+        // the bilinear transform maps the prototype's peak frequency
+        // w_a (relative to cutoff) to 2 * atan(g * w_a)
+        let w = two((g * w_a2.sqrt()).atan());
+        let freq = w * sample_rate / T::from(core::f64::consts::TAU).unwrap();
+        let gain = (two(r) * (T::one() - r * r).sqrt()).recip();
+
+        (freq, T::from(20.).unwrap() * gain.log10())
+    }
+
+    #[inline]
+    pub fn low_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        h_denominator(s, res).finv()
+    }
+
+    #[inline]
+    pub fn band_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        s.fdiv(h_denominator(s, res))
+    }
+
+    #[inline]
+    pub fn unit_band_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        band_pass(s, res).scale(two(res))
+    }
+
+    #[inline]
+    pub fn high_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        (s * s).fdiv(h_denominator(s, res))
+    }
+
+    #[inline]
+    pub fn all_pass<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        let bp1 = unit_band_pass(s, res);
+        bp1 + bp1 - Complex::one()
+    }
+
+    #[inline]
+    pub fn notch<T: Float>(s: Complex<T>, res: T) -> Complex<T> {
+        Complex::<T>::one() - unit_band_pass(s, res)
+    }
+
+    #[inline]
+    pub fn tilting<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        let m2 = gain.sqrt();
+        let m = m2.sqrt();
+        let sm = s.unscale(m);
+        (s * s + sm.scale(two(res)) + m2.recip()).fdiv(h_denominator(sm, res))
+    }
+
+    #[inline]
+    pub fn low_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        let m2 = gain.sqrt();
+        tilting(s, res, gain.recip()).scale(m2)
+    }
+
+    #[inline]
+    pub fn band_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        let m = gain.sqrt();
+        (s * (s + two(res) * m) + T::one()).fdiv(h_denominator(s, res / m))
+    }
+
+    /// Response of [`SVF::get_peaking`] with the (linear) `gain`. Like [`band_shelf`], it
+    /// takes the prototype resonance, i.e. half the `res` passed to [`SVF::process`]
+    #[inline]
+    pub fn peaking<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        (s * (s + two(res) * gain) + T::one()).fdiv(h_denominator(s, res))
+    }
+
+    #[inline]
+    pub fn high_shelf<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        let m2 = gain.sqrt();
+        tilting(s, res, gain).scale(m2)
+    }
+
+    /// Resonant peak, as realized by [`eq::ResonantPeak`]: two identical sections in series,
+    /// each with the response `(s^2 + res * sqrt(gain) * s + 1) / (s^2 + res * s + 1)`,
+    /// peaking at `gain` overall. Each section's poles are the resonance's, independent
+    /// of the gain.
+    #[inline]
+    pub fn resonant_peak_eq<T: Float>(s: Complex<T>, res: T, gain: T) -> Complex<T> {
+        let section = (s * (s + two(res) * gain.sqrt()) + T::one()).fdiv(h_denominator(s, res));
+        section * section
+    }
+
+    /// Frequency response of the digital filter itself, at the normalized frequency `omega`
+    /// (in radians per sample, in `[0 ; pi[`), of the output selected by `shape`, given the
+    /// `g` (which must be positive) and `res` coefficients passed to [`SVF::process`], and,
+    /// for the shelving shapes, the `root_gain` passed to the corresponding getter.
+    ///
+    /// Unlike the other functions in this module, which evaluate the analog prototypes, this
+    /// is evaluated from the filter's structure: each (trapezoidal) integrator, pre-gain
+    /// included, has the transfer function `g * (z + 1) / (z - 1)`, and, solving the
+    /// feedback loop, the highpass output is `1 / (1 + res * i + i^2)`, with `i` the
+    /// integrator's transfer function, the bandpass `i` times that, and the lowpass `i^2`
+    /// times that. The other outputs are combinations of these, as in the getters.
+    /// This includes the warping of the bilinear transform.
+    #[inline]
+    pub fn z_response(shape: FilterShape<f64>, g: f64, res: f64, omega: f64) -> Complex<f64> {
+        let one = Complex::<f64>::one();
+        let z = Complex::from_polar(1., omega);
+
+        // 1 / i, finite except at nyquist
+        let inv_int = (z - one) / ((z + one) * g);
+        let lp = (inv_int * (inv_int + res) + one).finv();
+        let bp = lp * inv_int;
+        let hp = bp * inv_int;
+        let bp1 = bp * res;
+
+        match shape {
+            FilterShape::Passthrough => one,
+            FilterShape::Lowpass => lp,
+            FilterShape::Bandpass => bp,
+            FilterShape::UnitBandpass => bp1,
+            FilterShape::Highpass => hp,
+            FilterShape::Allpass => bp1 * 2. - one,
+            FilterShape::Notch => one - bp1,
+            FilterShape::LowShelf(m) => (lp * m + bp1) * m + hp,
+            FilterShape::BandShelf(m) => bp1 * m + one - bp1,
+            FilterShape::HighShelf(m) => (hp * m + bp1) * m + lp,
+        }
+    }
+
+    /// Group delay, in samples, of the digital filter, at the normalized frequency `omega`,
+    /// i.e. [`group_delay`](crate::bode::group_delay) of [`z_response`], with the same
+    /// parameters.
+    #[inline]
+    pub fn group_delay(shape: FilterShape<f64>, g: f64, res: f64, omega: f64) -> f64 {
+        crate::bode::group_delay(|omega| z_response(shape, g, res, omega), omega)
+    }
+
+    /// Log-spaced [`sweep`](crate::bode::sweep) of the response of the digital filter, see
+    /// [`z_response`], with the same parameters.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn sweep(
+        shape: FilterShape<f64>,
+        g: f64,
+        res: f64,
+        sample_rate: f64,
+        f_lo: f64,
+        f_hi: f64,
+        points: usize,
+    ) -> Vec<(f64, f64, f64)> {
+        crate::bode::sweep(
+            |omega| z_response(shape, g, res, omega),
+            sample_rate,
+            f_lo,
+            f_hi,
+            points,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dc_blocked_highpass_converges_to_zero() {
+        let mut filter = DcBlockedHighpass::<4>::default();
+        let g = Simd::from_array([0.01, 0.05, 0.2, 1.]);
+        let res = Simd::from_array([0.5, 1., 1.4, 1.9]);
+
+        for _ in 0..20_000 {
+            filter.process(Simd::splat(1.), g, res);
+        }
+
+        let &hp = filter.get_highpass();
+        assert!(
+            hp.abs().simd_lt(Simd::splat(1e-12)).all(),
+            "DC leaked through: {hp:?}"
+        );
+    }
+
+    #[test]
+    fn dc_blocked_highpass_reset_clears_the_output() {
+        let mut filter = DcBlockedHighpass::<4>::default();
+        let g = Simd::splat(0.2);
+        let res = Simd::splat(1.);
+
+        for x in test_noise(3, 64) {
+            filter.process(x, g, res);
+        }
+
+        filter.reset();
+        assert_eq!(*filter.get_highpass(), Simd::splat(0.));
+    }
+
+    /// The digital filter is the bilinear transform of the analog prototypes of [`trnasfer`],
+    /// so its response at `omega` must be theirs at `s = j * tan(omega / 2) / g`
+    #[cfg(all(feature = "std", feature = "num"))]
+    #[test]
+    fn impulse_response_matches_analog_prototypes() {
+        use trnasfer::*;
+
+        let g = Simd::from_array([0.05, 0.3, 1., 4.]);
+        let res = Simd::from_array([0.3, 0.7, 1.4, 2.]);
+        let m = 2.;
+
+        // the analog shelves are centered on their midpoints, at `sqrt(m)` times (or the
+        // inverse of) the digital filter's cutoff, in the lowest (or highest) of them.
+        // The band shelf's analog prototype is the bell with a resonance divided by
+        // `sqrt(m)`, see `SVF::get_peaking`
+        type Prototype = fn(Complex<f64>, f64, f64) -> Complex<f64>;
+        let shapes: [(FilterShape<VFloat<4>>, f32, Prototype); 11] = [
+            (FilterShape::Passthrough, 1., |_, _, _| Complex::one()),
+            (FilterShape::Lowpass, 1., |s, r, _| low_pass(s, r)),
+            (FilterShape::Bandpass, 1., |s, r, _| band_pass(s, r)),
+            (FilterShape::UnitBandpass, 1., |s, r, _| {
+                unit_band_pass(s, r)
+            }),
+            (FilterShape::Highpass, 1., |s, r, _| high_pass(s, r)),
+            (FilterShape::Allpass, 1., |s, r, _| all_pass(s, r)),
+            (FilterShape::Notch, 1., |s, r, _| notch(s, r)),
+            (FilterShape::LowShelf(Simd::splat(m)), 1., |s, r, m| {
+                low_shelf(s.unscale(m.sqrt()), r, m * m)
+            }),
+            (FilterShape::BandShelf(Simd::splat(m)), 1., peaking),
+            (
+                FilterShape::BandShelf(Simd::splat(m)),
+                m.sqrt().recip(),
+                |s, r, m| band_shelf(s, r * m.sqrt(), m),
+            ),
+            (FilterShape::HighShelf(Simd::splat(m)), 1., |s, r, m| {
+                high_shelf(s.scale(m.sqrt()), r, m * m)
+            }),
+        ];
+
+        for (shape, res_scale, prototype) in shapes {
+            let coeffs = SvfCoeffs::new(g, res * Simd::splat(res_scale));
+            let ir = SVF::default().impulse_response(&coeffs, shape, 8192);
+
+            for k in 0..20 {
+                let omega = core::f64::consts::PI * k as f64 / 20.;
+                let measured = dtft(&ir, omega);
+
+                for lane in 0..4 {
+                    let s = Complex::new(0., (omega / 2.).tan() / f64::from(g[lane]));
+                    let r = f64::from(coeffs.res[lane]) / 2.;
+                    let expected = prototype(s, r, m.into());
+                    let error = (measured[lane] - expected).norm();
+                    assert!(
+                        error < 1e-4 * expected.norm() + 1e-5,
+                        "{shape:?}, lane {lane}, omega = {omega}: {} vs {expected}",
+                        measured[lane],
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn filter_slice_can_be_split_into_buffers() {
+        let input: Vec<_> = test_noise::<1>(3, 1001).iter().map(|x| x[0]).collect();
+        let (g, res) = (Simd::splat(0.2), Simd::splat(0.6));
+
+        let filtered = |splits: &[usize]| {
+            let mut buf = input.clone();
+            let mut svf = SVF::<1>::default();
+            let mut rest = buf.as_mut_slice();
+            for &len in splits {
+                let (head, tail) = rest.split_at_mut(len);
+                svf.filter_slice(head, g, res, FilterShape::Lowpass);
+                rest = tail;
+            }
+            svf.filter_slice(rest, g, res, FilterShape::Lowpass);
+            buf
+        };
+
+        let bits = |y: Vec<f32>| -> Vec<_> { y.into_iter().map(f32::to_bits).collect() };
+        let expected = bits(filtered(&[]));
+        assert_eq!(bits(filtered(&[333])), expected);
+        assert_eq!(bits(filtered(&[1, 0, 499, 7])), expected);
+    }
+
+    #[test]
+    fn limited_output_is_untouched_below_the_knee() {
+        let ceiling = Simd::splat(2.);
+        let mut svf = SVF::<4>::default();
+
+        for amplitude in [0.01, 0.5, 1., 1e3] {
+            let input = test_noise::<4>(4, 256);
+            for x in input {
+                svf.process(
+                    x * Simd::splat(amplitude),
+                    Simd::splat(0.3),
+                    Simd::splat(0.1),
+                );
+                let &y = svf.get_lowpass();
+                let limited = svf.get_limited(FilterShape::Lowpass, ceiling);
+
+                assert!(limited.abs().simd_le(ceiling).all(), "{limited:?}");
+                let below_knee = y.abs().simd_le(Simd::splat(1.));
+                assert!(
+                    below_knee.select(limited, y) == y,
+                    "{y:?} limited to {limited:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resumes_identically_after_serde_round_trip() {
+        let (g, res) = (Simd::from_array([0.01, 0.1, 1., 10.]), Simd::splat(0.3));
+        let input = test_noise::<4>(7, 1024);
+        let mut svf = SVF::<4>::default();
+        for &x in &input[..512] {
+            svf.process(x, g, res);
+        }
+
+        let mut restored = serde_round_trip(&svf);
+        let bits = |svf: &SVF<4>| {
+            let out = svf.outputs();
+            [out.passthrough, out.lowpass, out.bandpass, out.highpass].map(|y| y.to_bits())
+        };
+        assert_eq!(bits(&restored), bits(&svf));
+
+        for &x in &input[512..] {
+            svf.process(x, g, res);
+            restored.process(x, g, res);
+            assert_eq!(bits(&restored), bits(&svf));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn normalized_bandpass_rings_with_unit_amplitude() {
+        let freq_hz = Simd::from_array([100., 1000., 5000., 20000.]);
+        let g = g_from_hz(freq_hz, 48000.);
+        let w = Simd::from_array(g.to_array().map(|g| 2. * g.atan()));
+
+        let mut svf = SVF::<4>::default();
+        let ringing: Vec<_> = (0..4800)
+            .map(|i| {
+                let x = Simd::splat(if i == 0 { 1. } else { 0. });
+                svf.process(x, g, Simd::splat(0.));
+                svf.get_bandpass_normalized(g)
+            })
+            .collect();
+
+        // for y[n] = a * sin(w * n + phi): y[n]^2 - y[n - 1] * y[n + 1] = (a * sin(w))^2
+        for y in ringing[1..].windows(3) {
+            let amplitude = (y[1] * y[1] - y[0] * y[2]).sqrt() / w.sin();
+            assert!(
+                (amplitude - Simd::splat(1.))
+                    .abs()
+                    .simd_lt(Simd::splat(1e-3))
+                    .all(),
+                "amplitude: {amplitude:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fit_to_impulse_recovers_known_filter() {
+        let sample_rate = 48000.;
+        let (freq_hz, q) = (2000., 2.);
+        let coeffs = SvfCoeffs::<1>::new(
+            g_from_hz(Simd::splat(freq_hz), sample_rate),
+            res_from_q(Simd::splat(q)),
+        );
+        let target = SVF::default().to_fir(&coeffs, FilterShape::Bandpass, 512);
+
+        let (fitted, shape) = fit_to_impulse::<1>(&target, sample_rate);
+        assert_eq!(shape, FilterShape::Bandpass);
+        for (fitted, expected) in [(fitted.g, coeffs.g), (fitted.res, coeffs.res)] {
+            let error = ((fitted - expected) / expected).abs()[0];
+            assert!(error < 1e-3, "fitted {fitted:?}, expected {expected:?}");
+        }
+    }
+
+    #[test]
+    fn f64_lanes_match_f32_ones() {
+        let g = Simd::from_array([0.05, 0.3, 1., 4.]);
+        let res = Simd::from_array([0.3, 0.7, 1.4, 2.]);
+        let mut single = SVF::<4>::default();
+        let mut double = SVF::<4, f64>::default();
+
+        for x in test_noise(7, 2000) {
+            single.process(x, g, res);
+            double.process(x.cast(), g.cast(), res.cast());
+
+            for (a, b) in [
+                (*single.get_highpass(), *double.get_highpass()),
+                (*single.get_bandpass(), *double.get_bandpass()),
+                (*single.get_lowpass(), *double.get_lowpass()),
+                (single.get_notch(), double.get_notch()),
+            ] {
+                let error = (a.cast::<f64>() - b).abs();
+                assert!(
+                    error.simd_lt(Simd::splat(1e-4)).all(),
+                    "f32: {a:?}, f64: {b:?}"
+                );
+            }
+        }
+    }
+
+    /// Random coefficients, states and inputs, in their stable ranges, must
+    /// never make the outputs blow up, or become NaN or infinite
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn bounded_input_gives_bounded_output() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        const TRIALS: u32 = 64;
+        const SAMPLES: usize = 2048;
+        // res close to 0 is stable, but has unbounded gain at the cutoff
+        const MIN_RES: f32 = 0.1;
+        const BOUND: f32 = 1e3;
+
+        for trial in 1..=TRIALS {
+            let bytes = test_bytes(trial, (SAMPLES + 4) * 8 * 4);
+            let mut u = Unstructured::new(&bytes);
+
+            let mut svf = SVF::<8>::arbitrary(&mut u).unwrap();
+            let coeffs = SvfCoeffs::arbitrary(&mut u).unwrap();
+            let res = coeffs.res.simd_max(Simd::splat(MIN_RES));
+
+            for _ in 0..SAMPLES {
+                let x = arbitrary_vfloat(&mut u, -1.0..=1.).unwrap();
+                svf.process(x, coeffs.g, res);
+
+                let out = svf.outputs();
+                for y in [
+                    out.lowpass,
+                    out.bandpass,
+                    out.unit_bandpass,
+                    out.highpass,
+                    out.allpass,
+                    out.notch,
+                ] {
+                    assert!(
+                        y.is_finite().all() && y.abs().simd_le(Simd::splat(BOUND)).all(),
+                        "output out of bounds: {y:?}, g: {:?}, res: {res:?}",
+                        coeffs.g,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Largest magnitude of the lowpass output, in every lane, over samples `start..end`
+    /// of the impulse response of an `SVF` with the coefficients `g` and `res`
+    fn impulse_peak(g: VFloat<4>, res: VFloat<4>, start: usize, end: usize) -> VFloat<4> {
+        let mut svf = SVF::default();
+        let mut peak = Simd::splat(0f32);
+        for i in 0..end {
+            svf.process(Simd::splat(if i == 0 { 1. } else { 0. }), g, res);
+            if i >= start {
+                peak = peak.simd_max(svf.get_lowpass().abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn res_from_q_clamps_to_stable_range() {
+        let res = res_from_q(Simd::from_array([-1., f32::NAN, 0., f32::INFINITY]));
+        assert_eq!(res.to_array(), [0., 0., f32::MAX, 0.]);
+        assert!(stable_lanes(Simd::splat(1.), res).all());
+    }
+
+    #[test]
+    fn res_from_q_gives_decaying_impulse_responses() {
+        // from heavily overdamped (res = 20) to very resonant (res = 0.05)
+        let res = res_from_q(Simd::from_array([0.05, 0.5, 0.707, 20.]));
+
+        for g in [0.05, 1., 20.] {
+            let g = Simd::splat(g);
+            let peak = impulse_peak(g, res, 0, 64);
+            let tail = impulse_peak(g, res, 40_000, 41_000);
+            assert!(
+                (tail / peak).simd_lt(Simd::splat(1e-6)).all(),
+                "g: {g:?}, res: {res:?}, peak: {peak:?}, tail: {tail:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn undamped_filter_neither_decays_nor_grows() {
+        let g = Simd::from_array([0.01, 0.1, 1., 10.]);
+        let res = Simd::splat(0.);
+
+        // skipping the onset, whose first sample exceeds the ringing amplitude
+        let head = impulse_peak(g, res, 1000, 2000);
+        let tail = impulse_peak(g, res, 99_000, 100_000);
+        let ratio = tail / head;
+        assert!(
+            (ratio - Simd::splat(1.))
+                .abs()
+                .simd_lt(Simd::splat(1e-2))
+                .all(),
+            "head: {head:?}, tail: {tail:?}"
+        );
+    }
+
+    #[test]
+    fn negative_res_is_rejected() {
+        let g = Simd::from_array([0.01, 0.1, 1., 10.]);
+        let res = Simd::splat(-0.1);
+
+        assert!(!stable_lanes(g, res).any());
+        assert!(SvfCoeffs::try_new(g, res).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn unit_bandpass_peak_is_independent_of_res() {
+        let g = Simd::splat(0.2);
+        let coeffs = SvfCoeffs::new(g, Simd::from_array([0.05, 0.3, 1., 4.]));
+        let ir = SVF::default().impulse_response(&coeffs, FilterShape::UnitBandpass, 8192);
+
+        let w_c = unwarp(g);
+        let peak = dft_magnitude(&ir, w_c);
+        assert!(
+            (peak - Simd::splat(1.))
+                .abs()
+                .simd_lt(Simd::splat(1e-4))
+                .all(),
+            "peak: {peak:?}"
+        );
+
+        // and it is indeed the peak
+        for ratio in [0.9, 0.99, 1.01, 1.1] {
+            let gain = dft_magnitude(&ir, w_c * Simd::splat(ratio));
+            assert!(gain.simd_lt(peak).all(), "gain at {ratio} * w_c: {gain:?}");
+        }
+    }
+
+    /// Magnitude, at the cutoff, of the lowpass cascade given by `sections` (see
+    /// [`cascade_coeffs`]), measured from its impulse response
+    fn cascade_gain_at_cutoff<const S: usize>(
+        cutoff_hz: VFloat<4>,
+        sample_rate: f32,
+        sections: &[(f32, f32); S],
+    ) -> VFloat<4> {
+        let coeffs = cascade_coeffs(cutoff_hz, sample_rate, sections);
+        let mut svfs = [SVF::default(); S];
+
+        let ir: Vec<_> = (0..8192)
+            .map(|i| {
+                let x = Simd::splat(if i == 0 { 1. } else { 0. });
+                svfs.iter_mut().zip(&coeffs).fold(x, |x, (svf, coeffs)| {
+                    svf.process(x, coeffs.g, coeffs.res);
+                    *svf.get_lowpass()
+                })
+            })
+            .collect();
+
+        dft_magnitude(&ir, cutoff_hz * Simd::splat(2. * PI / sample_rate))
+    }
+
+    #[test]
+    fn cascade_is_3db_down_at_cutoff() {
+        // up to 0.99 * nyquist
+        let cutoff_hz = Simd::from_array([100., 1000., 12000., 23760.]);
+        let gain = cascade_gain_at_cutoff(cutoff_hz, 48000., &butterworth_sections::<4>());
+
+        let expected = Simd::splat(core::f32::consts::FRAC_1_SQRT_2);
+        assert!(
+            ((gain - expected) / expected)
+                .abs()
+                .simd_lt(Simd::splat(1e-3))
+                .all(),
+            "gain: {gain:?}"
+        );
+    }
+
+    #[test]
+    fn cascade_matches_prototype_at_cutoff() {
+        let sections = [(0.8, 1.2), (1.3, 0.6), (2., 1.9)];
+        let cutoff_hz = Simd::from_array([100., 1000., 12000., 23000.]);
+        let gain = cascade_gain_at_cutoff(cutoff_hz, 48000., &sections);
+
+        // |1 / ((j / freq_ratio)^2 + res * j / freq_ratio + 1)|
+        let expected = sections.iter().fold(1., |gain, &(freq_ratio, res)| {
+            let w = freq_ratio.recip();
+            gain / (1. - w * w).hypot(res * w)
+        });
+
+        let expected = Simd::splat(expected);
+        assert!(
+            ((gain - expected) / expected)
+                .abs()
+                .simd_lt(Simd::splat(1e-3))
+                .all(),
+            "gain: {gain:?}, expected: {expected:?}"
+        );
+    }
+
+    /// Run `input` through an `SVF<N>` driven by `g` and `res`, broadcast to every lane,
+    /// and return the lowpass, bandpass and highpass outputs of the first lane
+    fn lane_0_outputs<const N: usize>(input: &[f32], g: f32, res: f32) -> Vec<[f32; 3]>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        let mut svf = SVF::<N>::default();
+        input
+            .iter()
+            .map(|&x| {
+                svf.process(Simd::splat(x), Simd::splat(g), Simd::splat(res));
+                let (hp, bp, lp) = svf.raw_taps();
+                [lp[0], bp[0], hp[0]]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lane_count_doesnt_change_results() {
+        let input: Vec<_> = test_noise::<1>(42, 4096).iter().map(|x| x[0]).collect();
+
+        for (g, res) in [(0.01, 0.1), (0.3, 1.4), (5., 0.5), (MAX_G, 3.)] {
+            let expected = lane_0_outputs::<1>(&input, g, res);
+            for outputs in [
+                lane_0_outputs::<2>(&input, g, res),
+                lane_0_outputs::<4>(&input, g, res),
+                lane_0_outputs::<8>(&input, g, res),
+            ] {
+                // bitwise, so that even the signs of zeros must match
+                let bits = |y: &[[f32; 3]]| -> Vec<_> {
+                    y.iter().flatten().map(|y| y.to_bits()).collect()
+                };
+                assert_eq!(bits(&outputs), bits(&expected), "g: {g}, res: {res}");
+            }
+        }
+    }
+
+    #[test]
+    fn compensated_lowpass_has_flatter_passband() {
+        // resonant enough for the raw passband to rise well above 0 dB before the cutoff
+        let g = Simd::splat(0.05);
+        let res = Simd::from_array([0.3, 0.2, 0.5, 0.8]);
+
+        let mut svf = SVF::default();
+        let (raw, compensated): (Vec<_>, Vec<_>) = (0..8192)
+            .map(|i| {
+                svf.process(Simd::splat(if i == 0 { 1. } else { 0. }), g, res);
+                (*svf.get_lowpass(), svf.get_lowpass_compensated(res))
+            })
+            .unzip();
+
+        let w_c = unwarp(g);
+        let one = Simd::splat(1.);
+        for ratio in [0.1, 0.25, 0.5, 0.75] {
+            let w = w_c * Simd::splat(ratio);
+            let raw_error = (dft_magnitude(&raw, w) - one).abs();
+            let compensated_error = (dft_magnitude(&compensated, w) - one).abs();
+            assert!(
+                compensated_error.simd_lt(raw_error).all(),
+                "at {ratio} * w_c, raw: {raw_error:?}, compensated: {compensated_error:?}"
+            );
+        }
+
+        // the figures quoted in the docs, for res = 0.3
+        let db = |ir: &[VFloat<4>], ratio: f32| {
+            20. * dft_magnitude(ir, w_c * Simd::splat(ratio))[0].log10()
+        };
+        let octave = 0.5;
+        let third = 2f32.powf(-1. / 3.);
+        for (measured, expected) in [
+            (db(&raw, octave), 2.3),
+            (db(&raw, third), 7.1),
+            (db(&compensated, octave), -0.04),
+            (db(&compensated, third), -0.9),
+        ] {
+            assert!((measured - expected).abs() < 0.05, "{measured} dB");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lowpass_dc_gain_is_unity() {
+        let res = Simd::from_array([0.1, 0.7, core::f32::consts::SQRT_2, 4.]);
+
+        for g in [0.01, 0.1, 1., 10.] {
+            let coeffs = SvfCoeffs::new(Simd::splat(g), res);
+            let step = SVF::default().step_response(&coeffs, FilterShape::Lowpass, 100_000);
+
+            // up to the stalling of the lowpass integrator described in the docs
+            let error = (step.last().unwrap() - Simd::splat(1.)).abs();
+            assert!(
+                error.simd_lt(Simd::splat(2e-5)).all(),
+                "g: {g}, error: {error:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Frequency, in Hz, in every lane, at which the lowpass of an `SVF`
+    /// with the coefficients `g` and `res` peaks, searched for around `guess_hz`
+    fn lowpass_peak_hz(
+        g: VFloat<4>,
+        res: VFloat<4>,
+        guess_hz: VFloat<4>,
+        sample_rate: f32,
+    ) -> VFloat<4> {
+        let coeffs = SvfCoeffs::new(g, res);
+        let ir = SVF::default().impulse_response(&coeffs, FilterShape::Lowpass, 4096);
+        let to_omega = Simd::splat(2. * PI / sample_rate);
+
+        // coarse, then fine, grid search
+        let mut best = guess_hz;
+        for step in [20., 1.] {
+            let center = best;
+            let mut best_gain = Simd::splat(0.);
+            for i in -100..=100 {
+                let freq = center + Simd::splat(i as f32 * step);
+                let gain = dft_magnitude(&ir, freq * to_omega);
+                let better = gain.simd_gt(best_gain);
+                best = better.select(freq, best);
+                best_gain = better.select(gain, best_gain);
+            }
+        }
+        best
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn peak_correction_moves_peak_to_analog_frequency() {
+        let sample_rate = 48000.;
+        let res = Simd::splat(0.5);
+        let cutoff_hz = Simd::from_array([1000., 10000., 15000., 18000.]);
+        // sqrt(1 - res^2 / 2)
+        let analog_peak_hz = cutoff_hz * Simd::splat(0.875f32.sqrt());
+
+        let corrected = lowpass_peak_hz(
+            g_from_hz_peak_corrected(cutoff_hz, res, sample_rate),
+            res,
+            analog_peak_hz,
+            sample_rate,
+        );
+        let uncorrected = lowpass_peak_hz(
+            g_from_hz(cutoff_hz, sample_rate),
+            res,
+            analog_peak_hz,
+            sample_rate,
+        );
+
+        let corrected_error = (corrected - analog_peak_hz).abs();
+        let uncorrected_error = (uncorrected - analog_peak_hz).abs();
+        assert!(
+            corrected_error.simd_le(Simd::splat(2.)).all(),
+            "corrected: {corrected:?}, analog: {analog_peak_hz:?}"
+        );
+        // the uncorrected peaks drift further and further above, with the cutoff
+        assert!(
+            uncorrected_error[1] > 100.
+                && uncorrected_error[2] > 400.
+                && uncorrected_error[3] > 700.,
+            "uncorrected: {uncorrected:?}, analog: {analog_peak_hz:?}"
+        );
+
+        // the figures quoted in the docs, at 15 kHz
+        assert!((uncorrected[2] - 14500.).abs() < 50., "{uncorrected:?}");
+        assert!(
+            (analog_peak_hz[2] - 14000.).abs() < 50.,
+            "{analog_peak_hz:?}"
+        );
+    }
+
+    /// Deliberately straightforward, scalar, double precision implementation of the SVF's
+    /// difference equations, as written in Vadim Zavalishin's "The Art of VA Filter Design",
+    /// to cross-check the optimized `SVF::process` against
+    #[derive(Default)]
+    struct ReferenceSvf {
+        s1: f64,
+        s2: f64,
+    }
+
+    impl ReferenceSvf {
+        /// Returns the lowpass, bandpass and highpass outputs, in that order
+        fn process(&mut self, x: f64, g: f64, res: f64) -> [f64; 3] {
+            let hp = (x - (res + g) * self.s1 - self.s2) / (1. + g * res + g * g);
+
+            let v1 = g * hp;
+            let bp = v1 + self.s1;
+            self.s1 = bp + v1;
+
+            let v2 = g * bp;
+            let lp = v2 + self.s2;
+            self.s2 = lp + v2;
+
+            [lp, bp, hp]
+        }
+    }
+
+    #[test]
+    fn process_matches_reference_implementation() {
+        for trial in 0..16 {
+            // g log-uniform in [1e-3 ; 1e2], res uniform in [0.1 ; 3]
+            let params = test_noise::<4>(trial, 2);
+            let exponent = params[0].mul_add(Simd::splat(2.5), Simd::splat(-0.5));
+            let g = (exponent * Simd::splat(core::f32::consts::LOG2_10)).exp2();
+            let res = params[1].mul_add(Simd::splat(1.45), Simd::splat(1.55));
+
+            let mut svf = SVF::default();
+            let mut reference: [ReferenceSvf; 4] = Default::default();
+
+            for x in test_noise::<4>(trial + 100, 2048) {
+                svf.process(x, g, res);
+                let (hp, bp, lp) = svf.raw_taps();
+
+                for (lane, reference) in reference.iter_mut().enumerate() {
+                    let expected =
+                        reference.process(x[lane].into(), g[lane].into(), res[lane].into());
+
+                    for (y, expected) in [lp[lane], bp[lane], hp[lane]].into_iter().zip(expected) {
+                        // single precision, with some cancellation in the highpass at high g
+                        let error = (f64::from(y) - expected).abs();
+                        assert!(
+                            error <= 1e-3 * expected.abs().max(1.),
+                            "got {y}, expected {expected}, g: {}, res: {}",
+                            g[lane],
+                            res[lane],
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic_path_is_reproducible() {
+        let g = Simd::from_array([0.01, 0.3, 2., 50.]);
+        let res = Simd::from_array([0.1, 0.7, 1.4, 3.]);
+
+        // the same operations, in the same order, one lane at a time,
+        // `s1` and `s2` being the bandpass and lowpass integrators' states
+        let mut svf = SVF::default();
+        let mut states = [[0f32; 2]; 4];
+        for x in test_noise::<4>(7, 4096) {
+            svf.process_deterministic(x, g, res);
+            let (hp, bp, lp) = svf.raw_taps();
+
+            for (lane, [s1, s2]) in states.iter_mut().enumerate() {
+                let (x, g, res) = (x[lane], g[lane], res[lane]);
+                let g1 = res + g;
+                let expected_hp = (x - *s2 - g1 * *s1) / (g1 * g + 1.);
+                let v1 = expected_hp * g;
+                let expected_bp = v1 + *s1;
+                *s1 = expected_bp + v1;
+                let v2 = expected_bp * g;
+                let expected_lp = v2 + *s2;
+                *s2 = expected_lp + v2;
+
+                assert_eq!(hp[lane].to_bits(), expected_hp.to_bits());
+                assert_eq!(bp[lane].to_bits(), expected_bp.to_bits());
+                assert_eq!(lp[lane].to_bits(), expected_lp.to_bits());
+            }
+        }
+
+        // and, across platforms, to a recorded reference
+        let input = [1., -0.5, 0.25, 0., 0., 0.75, -1., 0.];
+        let mut svf = SVF::<1>::default();
+        let lowpass = input.map(|x| {
+            svf.process_deterministic(Simd::splat(x), Simd::splat(0.3), Simd::splat(0.7));
+            svf.get_lowpass()[0].to_bits()
+        });
+        assert_eq!(
+            lowpass,
+            [
+                0x3d8d_c8de,
+                0x3e4d_9674,
+                0x3e80_c752,
+                0x3e5d_69ba,
+                0x3e19_5ad8,
+                0x3deb_f912,
+                0x3dc1_a2b0,
+                0xbd03_c0d6,
+            ]
+        );
     }
 }
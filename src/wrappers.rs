@@ -0,0 +1,704 @@
+use super::*;
+
+use halfband::HalfbandFir;
+
+/// Runs a filter in parallel with its dry input, delaying the latter by the filter's
+/// estimated latency (see [`Filter::latency`]) before mixing both, to reduce the phase
+/// cancellation that occurs when blending a filtered signal with its unfiltered version.
+///
+/// The dry path is delayed by at most `MAX_DELAY - 1` samples.
+#[derive(Clone, Copy)]
+pub struct Parallel<F, const N: usize = FLOATS_PER_VECTOR, const MAX_DELAY: usize = 64>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filter: F,
+    dry: [VFloat<N>; MAX_DELAY],
+    pos: usize,
+}
+
+impl<F: Default, const N: usize, const MAX_DELAY: usize> Default for Parallel<F, N, MAX_DELAY>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(F::default())
+    }
+}
+
+impl<F, const N: usize, const MAX_DELAY: usize> Parallel<F, N, MAX_DELAY>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(filter: F) -> Self {
+        assert_ne!(
+            MAX_DELAY, 0,
+            "the dry delay line must hold at least one sample"
+        );
+        Self {
+            filter,
+            dry: [Simd::splat(0.); MAX_DELAY],
+            pos: 0,
+        }
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    #[inline]
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+}
+
+impl<F: Filter<N>, const N: usize, const MAX_DELAY: usize> Parallel<F, N, MAX_DELAY>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Reset both the inner filter and the dry delay line
+    #[inline]
+    pub fn reset(&mut self) {
+        self.filter.reset();
+        self.dry = [Simd::splat(0.); MAX_DELAY];
+    }
+
+    /// Process the input sample `x` and return the mix of the (delayed) dry signal
+    /// and the inner filter's output selected by `shape`.
+    ///
+    /// `mix = 0.0` outputs only the dry signal, `mix = 1.0` outputs only the filtered signal.
+    #[inline]
+    pub fn process(
+        &mut self,
+        x: VFloat<N>,
+        coeffs: &F::Coeffs,
+        shape: F::Shape,
+        mix: VFloat<N>,
+    ) -> VFloat<N> {
+        self.filter.process(x, coeffs);
+        let wet = self.filter.get(shape);
+
+        self.dry[self.pos] = x;
+
+        let delays = self.filter.latency(coeffs).round().to_array();
+        let mut dry = Simd::splat(0.);
+        for (lane, delay) in delays.into_iter().enumerate() {
+            // saturating cast, NaNs become 0
+            let delay = (delay as usize).min(MAX_DELAY - 1);
+            dry[lane] = self.dry[(self.pos + MAX_DELAY - delay) % MAX_DELAY][lane];
+        }
+
+        self.pos = (self.pos + 1) % MAX_DELAY;
+
+        mix.mul_add(wet - dry, dry)
+    }
+}
+
+/// Wraps a filter, allowing it to be bypassed without clicks, by crossfading between
+/// its output and its dry input over a fixed number of samples.
+///
+/// The inner filter keeps running while bypassed, so that it doesn't restart
+/// from a stale state when re-enabled.
+#[derive(Clone, Copy)]
+pub struct Bypassable<F, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filter: F,
+    mix: smooth::Ramp<N>,
+    fade_len: usize,
+    bypassed: bool,
+}
+
+impl<F, const N: usize> Bypassable<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create a new, enabled, bypass wrapper, crossfading over `fade_len` samples when toggled
+    #[inline]
+    pub fn new(filter: F, fade_len: usize) -> Self {
+        Self {
+            filter,
+            mix: smooth::Ramp::new(Simd::splat(1.)),
+            fade_len,
+            bypassed: false,
+        }
+    }
+
+    /// Start crossfading towards the dry signal (if `bypass` is `true`), or
+    /// towards the filtered signal (if `false`). Does nothing if already there.
+    #[inline]
+    pub fn set_bypass(&mut self, bypass: bool) {
+        if bypass != self.bypassed {
+            self.bypassed = bypass;
+            let target = if bypass { 0. } else { 1. };
+            self.mix.set_target(Simd::splat(target), self.fade_len);
+        }
+    }
+
+    #[inline]
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed
+    }
+
+    #[inline]
+    pub fn set_fade_len(&mut self, fade_len: usize) {
+        self.fade_len = fade_len;
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    #[inline]
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+}
+
+impl<F: Filter<N>, const N: usize> Bypassable<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+
+    /// Process the input sample `x` and return the inner filter's output selected by `shape`,
+    /// crossfaded with `x` according to the current bypass state
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, coeffs: &F::Coeffs, shape: F::Shape) -> VFloat<N> {
+        self.filter.process(x, coeffs);
+        let wet = self.filter.get(shape);
+        self.mix.tick().mul_add(wet - x, x)
+    }
+}
+
+/// Wraps a filter, smoothing its coefficients over blocks of exactly `BLOCK` samples.
+///
+/// For each block, the per-sample coefficient increments are computed only once, and the
+/// coefficients then move linearly (and thus monotonically) from the previous block's target
+/// to the new one, landing on it exactly on the block's last sample. The block length being
+/// known at compile time, processing doesn't allocate, and the loop can be fully unrolled.
+#[derive(Clone, Copy)]
+pub struct BlockSmoothed<F: Filter<N>, const BLOCK: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filter: F,
+    coeffs: F::Coeffs,
+}
+
+impl<F: Filter<N>, const BLOCK: usize, const N: usize> BlockSmoothed<F, BLOCK, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    F::Coeffs: smooth::LinearCoeffs,
+    F::Shape: Copy,
+{
+    /// Wrap `filter`, with its coefficients starting at `coeffs`
+    #[inline]
+    pub fn new(filter: F, coeffs: F::Coeffs) -> Self {
+        assert_ne!(BLOCK, 0, "blocks must hold at least one sample");
+        Self { filter, coeffs }
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    #[inline]
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+
+    /// The coefficients reached at the end of the last block
+    #[inline]
+    pub fn coeffs(&self) -> &F::Coeffs {
+        &self.coeffs
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+
+    /// Process a block of input samples, while moving the coefficients towards
+    /// `target_coeffs`, and return the inner filter's outputs selected by `shape`
+    #[inline]
+    pub fn process_block(
+        &mut self,
+        input: &[VFloat<N>; BLOCK],
+        target_coeffs: F::Coeffs,
+        shape: F::Shape,
+    ) -> [VFloat<N>; BLOCK] {
+        use smooth::LinearCoeffs;
+
+        let inc = F::Coeffs::increment(&self.coeffs, &target_coeffs, BLOCK);
+
+        core::array::from_fn(|i| {
+            if i == BLOCK - 1 {
+                // land exactly on the target, to avoid accumulating rounding errors
+                self.coeffs = target_coeffs;
+            } else {
+                self.coeffs.advance(&inc);
+            }
+            self.filter.process(input[i], &self.coeffs);
+            self.filter.get(shape)
+        })
+    }
+}
+
+/// Runs a filter on a complex-valued signal, e.g. an analytic signal, as two copies of it,
+/// one for the real part, and one for the imaginary part, sharing the same coefficients.
+///
+/// The filters in this crate have real coefficients, so filtering a complex signal is
+/// exactly filtering both of its parts independently. In particular, filtering an analytic
+/// signal keeps it analytic, the negative frequencies staying silent.
+///
+/// Samples are passed as their `(real, imaginary)` parts, or, with the `num`
+/// feature, as `Complex`es of vectors.
+#[derive(Default, Clone, Copy)]
+pub struct ComplexFilter<F, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    re: F,
+    im: F,
+}
+
+impl<F: Clone, const N: usize> ComplexFilter<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Wrap `filter`, used, along with a copy of it, for both parts of the signal
+    #[inline]
+    pub fn new(filter: F) -> Self {
+        Self {
+            re: filter.clone(),
+            im: filter,
+        }
+    }
+}
+
+impl<F: Filter<N>, const N: usize> ComplexFilter<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    F::Shape: Copy,
+{
+    #[inline]
+    pub fn reset(&mut self) {
+        self.re.reset();
+        self.im.reset();
+    }
+
+    /// The filters processing the real and imaginary parts, respectively
+    #[inline]
+    pub fn parts(&self) -> (&F, &F) {
+        (&self.re, &self.im)
+    }
+
+    /// Feed the complex input sample `re + j * im`
+    #[inline]
+    pub fn process_parts(&mut self, re: VFloat<N>, im: VFloat<N>, coeffs: &F::Coeffs) {
+        self.re.process(re, coeffs);
+        self.im.process(im, coeffs);
+    }
+
+    /// Get the output corresponding to `shape`, as its `(real, imaginary)` parts
+    #[inline]
+    pub fn get_parts(&self, shape: F::Shape) -> (VFloat<N>, VFloat<N>) {
+        (self.re.get(shape), self.im.get(shape))
+    }
+
+    /// Feed the complex input sample `x`
+    #[cfg(feature = "num")]
+    #[inline]
+    pub fn process_complex(&mut self, x: Complex<VFloat<N>>, coeffs: &F::Coeffs) {
+        self.process_parts(x.re, x.im, coeffs);
+    }
+
+    /// Get the output corresponding to `shape`
+    #[cfg(feature = "num")]
+    #[inline]
+    pub fn get_complex(&self, shape: F::Shape) -> Complex<VFloat<N>> {
+        let (re, im) = self.get_parts(shape);
+        Complex::new(re, im)
+    }
+}
+
+/// `M` filters in series, the output of each stage, selected by the same shape, being
+/// the input of the next one, for slopes steeper than a single filter's.
+///
+/// To build a Butterworth lowpass of order `2 * M` out of [`SVF`](crate::svf::SVF)s, use the
+/// lowpass shape, and the coefficients returned by [`svf::cascade_coeffs`] for
+/// [`svf::butterworth_sections`], e.g. `butterworth_sections::<2>()` for a 4th order filter,
+/// whose stages have `res = 2 * sin(pi / 8)` and `2 * sin(3 * pi / 8)` (about `0.765` and
+/// `1.848`), and `butterworth_sections::<3>()` for a 6th order one (`res` of about `0.518`,
+/// `1.414` and `1.932`), all at the same cutoff. Identical stages (e.g. `M` identical
+/// [`OnePole`](crate::one_pole::OnePole)s) steepen the slope just as well, with a softer knee,
+/// the cutoff (`-3 dB` point) of the whole cascade falling below that of each stage.
+#[derive(Clone, Copy)]
+pub struct Cascade<F, const M: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    stages: [F; M],
+}
+
+impl<F: Default, const M: usize, const N: usize> Default for Cascade<F, M, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(core::array::from_fn(|_| F::default()))
+    }
+}
+
+impl<F, const M: usize, const N: usize> Cascade<F, M, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(stages: [F; M]) -> Self {
+        Self { stages }
+    }
+
+    #[inline]
+    pub fn stages(&self) -> &[F; M] {
+        &self.stages
+    }
+
+    #[inline]
+    pub fn stages_mut(&mut self) -> &mut [F; M] {
+        &mut self.stages
+    }
+}
+
+impl<F: Filter<N>, const M: usize, const N: usize> Cascade<F, M, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    F::Shape: Copy,
+{
+    /// Reset every stage
+    #[inline]
+    pub fn reset(&mut self) {
+        self.stages.iter_mut().for_each(F::reset);
+    }
+
+    /// Run `x` through every stage, in order, stage `i` using `coeffs[i]`, and
+    /// return the output, selected by `shape`, of the last one (or `x` if `M == 0`)
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, coeffs: &[F::Coeffs; M], shape: F::Shape) -> VFloat<N> {
+        chain(&mut self.stages, coeffs, core::iter::repeat(shape), x)
+    }
+}
+
+/// Run `x` through `filters`, in order, each one with the coefficients and shape at the same
+/// index, and return the output of the last one (or `x` if `filters` is empty), ignoring the
+/// excess coefficients or shapes, if any. Shared by [`Cascade::process`] and
+/// [`SVF::process_chain`](crate::svf::SVF::process_chain).
+#[inline]
+pub(crate) fn chain<F: Filter<N>, const N: usize>(
+    filters: &mut [F],
+    coeffs: &[F::Coeffs],
+    shapes: impl IntoIterator<Item = F::Shape>,
+    x: VFloat<N>,
+) -> VFloat<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filters
+        .iter_mut()
+        .zip(coeffs)
+        .zip(shapes)
+        .fold(x, |x, ((filter, coeffs), shape)| {
+            filter.process(x, coeffs);
+            filter.get(shape)
+        })
+}
+
+/// `CH` independent instances of a filter, sharing the same coefficients, one per channel of a
+/// multichannel (e.g. stereo, or surround) signal.
+///
+/// Unlike the lanes of a vector, usually holding independent voices, each with their own
+/// coefficients, the channels are filtered identically, and each can still pack `N` voices.
+#[derive(Clone, Copy)]
+pub struct MultiChannel<F, const CH: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    channels: [F; CH],
+}
+
+impl<F: Default, const CH: usize, const N: usize> Default for MultiChannel<F, CH, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(core::array::from_fn(|_| F::default()))
+    }
+}
+
+impl<F, const CH: usize, const N: usize> MultiChannel<F, CH, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(channels: [F; CH]) -> Self {
+        Self { channels }
+    }
+
+    #[inline]
+    pub fn channels(&self) -> &[F; CH] {
+        &self.channels
+    }
+
+    #[inline]
+    pub fn channels_mut(&mut self) -> &mut [F; CH] {
+        &mut self.channels
+    }
+}
+
+impl<F: Filter<N>, const CH: usize, const N: usize> MultiChannel<F, CH, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    F::Shape: Copy,
+{
+    /// Reset every channel
+    #[inline]
+    pub fn reset(&mut self) {
+        self.channels.iter_mut().for_each(F::reset);
+    }
+
+    /// Feed `xs[i]` to channel `i`, every channel using `coeffs`, and
+    /// return their outputs, selected by `shape`, in the same order
+    #[inline]
+    pub fn process(
+        &mut self,
+        xs: &[VFloat<N>; CH],
+        coeffs: &F::Coeffs,
+        shape: F::Shape,
+    ) -> [VFloat<N>; CH] {
+        for (channel, &x) in self.channels.iter_mut().zip(xs) {
+            channel.process(x, coeffs);
+        }
+        self.get(shape)
+    }
+
+    /// Get every channel's output selected by `shape`
+    #[inline]
+    pub fn get(&self, shape: F::Shape) -> [VFloat<N>; CH] {
+        core::array::from_fn(|i| self.channels[i].get(shape))
+    }
+}
+
+/// Wraps a filter, skipping its processing while it is fed silence, once settled, e.g. for
+/// the idle filters of a large bank.
+///
+/// The filter is considered settled once its input and selected output have stayed within
+/// `threshold` of `0.0`, in every lane, for `hold` consecutive samples. It is then reset,
+/// zeroing its states exactly (which also keeps them from decaying into denormals), and, as
+/// long as the input stays within the threshold, `process` returns `0.0` without running it.
+/// The first input sample above the threshold wakes it up, and it resumes from its reset
+/// state. The threshold should be well below audibility (e.g. `1e-6`, `-120 dB`), and `hold`
+/// long enough to cover the quiet stretches of the filter's ringing, i.e. around the
+/// period of its lowest resonance.
+#[derive(Clone, Copy)]
+pub struct SilenceGate<F, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filter: F,
+    threshold: f32,
+    hold: usize,
+    quiet: usize,
+}
+
+impl<F, const N: usize> SilenceGate<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(filter: F, threshold: f32, hold: usize) -> Self {
+        assert_ne!(hold, 0, "the hold time must be at least one sample");
+        Self {
+            filter,
+            threshold,
+            hold,
+            quiet: 0,
+        }
+    }
+
+    /// Returns `true` if the filter is settled, and its processing skipped on silent inputs
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.quiet >= self.hold
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// The filter may be modified, as long as [`Self::reset`] is called
+    /// if its states are, as the gate couldn't otherwise tell
+    #[inline]
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+
+    #[inline]
+    fn is_quiet(&self, x: VFloat<N>) -> bool {
+        x.abs().simd_le(Simd::splat(self.threshold)).all()
+    }
+}
+
+impl<F: Filter<N>, const N: usize> SilenceGate<F, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Reset the filter, and the silence detection
+    #[inline]
+    pub fn reset(&mut self) {
+        self.filter.reset();
+        self.quiet = 0;
+    }
+
+    /// Process the input sample `x` and return the filter's output selected by `shape`,
+    /// or `0.0`, without processing, if it is settled, and `x` is silent
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, coeffs: &F::Coeffs, shape: F::Shape) -> VFloat<N> {
+        let quiet_input = self.is_quiet(x);
+
+        if quiet_input && self.is_settled() {
+            return Simd::splat(0.);
+        }
+
+        self.filter.process(x, coeffs);
+        let y = self.filter.get(shape);
+
+        if quiet_input && self.is_quiet(y) {
+            self.quiet += 1;
+            if self.is_settled() {
+                self.filter.reset();
+            }
+        } else {
+            self.quiet = 0;
+        }
+
+        y
+    }
+}
+
+/// Number of taps of the halfband filters of an [`Oversampled`] filter, see [`HalfbandFir`]
+const OVERSAMPLING_TAPS: usize = 16;
+
+/// Runs a filter at `FACTOR` (`2` or `4`) times the sample rate, reducing the aliasing it
+/// produces, e.g. when modulated at audio rate, or with nonlinearities, as well as the
+/// warping of its response near nyquist.
+///
+/// The input is upsampled, and the output downsampled, by one (`2x`) or two (`4x`) stages
+/// of [`HalfbandFir`] filters, attenuating the images and aliases above `0.37` times the
+/// (stage's) high sample rate by more than `90 dB`. The filter's coefficients must be
+/// computed for the high sample rate, i.e. `FACTOR * sample_rate`. The resampling stages add
+/// a latency, at the original sample rate, of `14.5` samples at `2x`, and `21.75` at `4x`.
+#[derive(Clone, Copy)]
+pub struct Oversampled<F, const FACTOR: usize, const N: usize = FLOATS_PER_VECTOR>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    filter: F,
+    up: [HalfbandFir<OVERSAMPLING_TAPS, N>; 2],
+    down: [HalfbandFir<OVERSAMPLING_TAPS, N>; 2],
+}
+
+impl<F: Default, const FACTOR: usize, const N: usize> Default for Oversampled<F, FACTOR, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(F::default())
+    }
+}
+
+impl<F, const FACTOR: usize, const N: usize> Oversampled<F, FACTOR, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    pub fn new(filter: F) -> Self {
+        assert!(
+            FACTOR == 2 || FACTOR == 4,
+            "the oversampling factor must be 2 or 4"
+        );
+        Self {
+            filter,
+            up: [HalfbandFir::new(); 2],
+            down: [HalfbandFir::new(); 2],
+        }
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    #[inline]
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+}
+
+impl<F: Filter<N>, const FACTOR: usize, const N: usize> Oversampled<F, FACTOR, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    F::Shape: Copy,
+{
+    /// Reset the filter, and the resampling stages
+    #[inline]
+    pub fn reset(&mut self) {
+        self.filter.reset();
+        for stage in self.up.iter_mut().chain(&mut self.down) {
+            stage.reset();
+        }
+    }
+
+    /// Feed one sample, at the original sample rate, to the filter, running it `FACTOR`
+    /// times with `coeffs`, and return the output selected by `shape`, downsampled
+    #[inline]
+    pub fn process(&mut self, x: VFloat<N>, coeffs: &F::Coeffs, shape: F::Shape) -> VFloat<N> {
+        let mut high = [Simd::splat(0.); 4];
+
+        let [x0, x1] = self.up[0].upsample(x);
+        if FACTOR == 4 {
+            let [y0, y1] = self.up[1].upsample(x0);
+            let [y2, y3] = self.up[1].upsample(x1);
+            high = [y0, y1, y2, y3];
+        } else {
+            high[..2].copy_from_slice(&[x0, x1]);
+        }
+
+        for y in &mut high[..FACTOR] {
+            self.filter.process(*y, coeffs);
+            *y = self.filter.get(shape);
+        }
+
+        let [y0, y1, y2, y3] = high;
+        if FACTOR == 4 {
+            let z0 = self.down[1].downsample([y0, y1]);
+            let z1 = self.down[1].downsample([y2, y3]);
+            self.down[0].downsample([z0, z1])
+        } else {
+            self.down[0].downsample([y0, y1])
+        }
+    }
+}